@@ -5,18 +5,158 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
 ////////////////////////////////////////////////////////////////////////////////
 
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
 use serde::{Deserialize, Serialize};
 
+use crate::version;
+
+/// The URL asset objects are downloaded from, the same as [`crate::ASSET_BASE_PATH`]
+pub const DEFAULT_RESOURCES_URL: &str = crate::ASSET_BASE_PATH;
+
 /// Information about assets used by the game
+///
+/// This is the file that `AssetIndex.url` in the version JSON points to.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct AssetIndex {
+    /// The assets, keyed by their name, ie `minecraft/sounds/ambient/cave/cave1.ogg`
     #[serde(with = "tuple_vec_map")]
     pub objects: Vec<(String, Object)>,
+    /// Set by the 1.6 index, meaning assets must be copied into the game's `resources` directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub map_to_resources: Option<bool>,
+    /// Set by pre-1.7 indexes, meaning assets must be copied to paths named after their keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r#virtual: Option<bool>,
+}
+
+impl AssetIndex {
+    /// Whether assets must be copied to paths named after their keys, for pre-1.7 versions
+    pub fn is_virtual(&self) -> bool {
+        self.r#virtual.unwrap_or(false)
+    }
+
+    /// Whether assets must be copied into the game's `resources` directory, for 1.6 versions
+    pub fn maps_to_resources(&self) -> bool {
+        self.map_to_resources.unwrap_or(false)
+    }
+
+    /// Get an object by its key
+    pub fn get(&self, key: &str) -> Option<&Object> {
+        self.objects
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, object)| object)
+    }
+
+    /// The sum of the sizes of all objects
+    pub fn total_size(&self) -> u64 {
+        self.objects.iter().map(|(_, object)| object.size).sum()
+    }
+
+    /// Check that the objects add up to the total size given by the version that points to this
+    /// index, which catches truncated or stale indexes
+    pub fn verify_total(&self, index: &version::AssetIndex) -> Result<(), SizeMismatch> {
+        let actual = self.total_size();
+        if actual == index.total_size {
+            Ok(())
+        } else {
+            Err(SizeMismatch {
+                expected: index.total_size,
+                actual,
+            })
+        }
+    }
+
+    /// The relative path an asset must be stored at
+    ///
+    /// For virtual and `map_to_resources` layouts this is the key itself, relative to the virtual
+    /// or resources directory. Otherwise it is the hashed path relative to the objects directory.
+    /// Returns `None` if there is no object with that key.
+    pub fn destination(&self, key: &str) -> Option<String> {
+        let object = self.get(key)?;
+        if self.is_virtual() || self.maps_to_resources() {
+            Some(key.to_owned())
+        } else {
+            Some(object.path())
+        }
+    }
+}
+
+/// How the assets of a version are laid out on disk, based on its assets id
+///
+/// See [`Version::asset_layout`](crate::version::Version::asset_layout).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum AssetLayout {
+    /// Assets are stored by hash in the shared `objects` directory, as every version since 1.7 does
+    Hashed,
+    /// The `legacy` assets of 1.7 and earlier, copied to paths named after their keys in a
+    /// virtual directory
+    Legacy,
+    /// The `pre-1.6` assets, copied into the game's `resources` directory
+    Pre16,
+}
+
+impl AssetLayout {
+    /// The layout used by an assets id
+    pub fn from_assets_id(id: &str) -> Self {
+        match id {
+            "legacy" => AssetLayout::Legacy,
+            "pre-1.6" => AssetLayout::Pre16,
+            _ => AssetLayout::Hashed,
+        }
+    }
 }
 
+/// Error returned when the objects of an asset index don't add up to the expected total size
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct SizeMismatch {
+    /// The `total_size` the version file specifies
+    pub expected: u64,
+    /// The sum of the sizes of the objects
+    pub actual: u64,
+}
+
+impl fmt::Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "asset objects total {} bytes, expected {}",
+            self.actual, self.expected
+        )
+    }
+}
+
+impl crate::error::StdError for SizeMismatch {}
+
+/// A single asset
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Object {
     pub hash: String,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
     pub size: u64,
 }
+
+impl Object {
+    /// The path of the object relative to the objects directory, in the form `ab/abcdef...`
+    pub fn path(&self) -> String {
+        let prefix = self.hash.get(..2).unwrap_or(&self.hash);
+        format!("{}/{}", prefix, self.hash)
+    }
+
+    /// The URL to download the object from, joining [`Object::path`] onto a resources base URL
+    ///
+    /// Pass [`DEFAULT_RESOURCES_URL`] for Mojang's servers, or the URL of a mirror.
+    pub fn url(&self, base: &str) -> String {
+        if base.ends_with('/') {
+            format!("{}{}", base, self.path())
+        } else {
+            format!("{}/{}", base, self.path())
+        }
+    }
+}
@@ -0,0 +1,387 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Lenient deserializers for fields that third party tools don't always write the same way as
+//! Mojang.
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+/// Deserialize a `u64` from either a number or a string containing one, ie `"size": "426900"`
+pub(crate) fn u64_or_string<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct U64Visitor;
+
+    impl<'de> Visitor<'de> for U64Visitor {
+        type Value = u64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a non-negative integer or a string containing one")
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(value)
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            u64::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            value
+                .parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(U64Visitor)
+}
+
+/// Deserialize a `u16` from either a number or a string containing one, ie `"majorVersion": "21"`
+pub(crate) fn u16_or_string<'de, D>(deserializer: D) -> Result<u16, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = u64_or_string(deserializer)?;
+    u16::try_from(value).map_err(|_| {
+        de::Error::invalid_value(de::Unexpected::Unsigned(value), &"an integer up to 65535")
+    })
+}
+
+/// Deserialize an optional list that some tools write as an empty object when there is nothing in
+/// it, ie `"rules": {}`, which becomes `None`
+///
+/// Objects with any entries are still rejected.
+pub(crate) fn seq_or_empty_map<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    struct SeqVisitor<T>(PhantomData<T>);
+
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for SeqVisitor<T> {
+        type Value = Option<Vec<T>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array or an empty object")
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            Vec::deserialize(de::value::SeqAccessDeserializer::new(seq)).map(Some)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            match map.next_key::<de::IgnoredAny>()? {
+                Some(_) => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+                None => Ok(None),
+            }
+        }
+    }
+
+    deserializer.deserialize_any(SeqVisitor(PhantomData))
+}
+
+/// A boolean that some tools write as `1`/`0` or `"true"`/`"false"`
+///
+/// Any non-zero integer is `true`. Other strings are rejected.
+struct LenientBool(bool);
+
+impl<'de> Deserialize<'de> for LenientBool {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BoolVisitor;
+
+        impl<'de> Visitor<'de> for BoolVisitor {
+            type Value = LenientBool;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a boolean, an integer, or \"true\" or \"false\"")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(LenientBool(value))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(LenientBool(value != 0))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(LenientBool(value != 0))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "true" => Ok(LenientBool(true)),
+                    "false" => Ok(LenientBool(false)),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(BoolVisitor)
+    }
+}
+
+/// Deserialize an optional boolean written in any of the shapes [`LenientBool`] accepts
+pub(crate) fn option_lenient_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<LenientBool>::deserialize(deserializer)?.map(|value| value.0))
+}
+
+/// Deserialize a map of booleans written in any of the shapes [`LenientBool`] accepts
+pub(crate) fn map_of_lenient_bool<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = BTreeMap::<String, LenientBool>::deserialize(deserializer)?;
+    Ok(map.into_iter().map(|(key, value)| (key, value.0)).collect())
+}
+
+/// Skips the keys of a struct starting with `_`, such as the `_comment_` arrays some tools add,
+/// before they reach the derived implementation of the struct
+///
+/// This wraps the deserializer passed to the derived implementation, and in turn the visitor and
+/// map it gets, so the struct can still deny any other unknown field and borrow from the input.
+pub(crate) struct SkipUnderscoreKeys<T>(pub(crate) T);
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for SkipUnderscoreKeys<D> {
+    type Error = D::Error;
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_any(SkipUnderscoreKeys(visitor))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0
+            .deserialize_struct(name, fields, SkipUnderscoreKeys(visitor))
+    }
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for SkipUnderscoreKeys<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        self.0.visit_map(SkipUnderscoreKeys(map))
+    }
+
+    fn visit_seq<S>(self, seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        self.0.visit_seq(seq)
+    }
+}
+
+impl<'de, M: MapAccess<'de>> MapAccess<'de> for SkipUnderscoreKeys<M> {
+    type Error = M::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        loop {
+            match self.0.next_key::<CowStr>()? {
+                None => return Ok(None),
+                Some(CowStr(key)) if key.starts_with('_') => {
+                    self.0.next_value::<de::IgnoredAny>()?;
+                }
+                Some(CowStr(Cow::Borrowed(key))) => {
+                    return seed
+                        .deserialize(de::value::BorrowedStrDeserializer::new(key))
+                        .map(Some);
+                }
+                Some(CowStr(Cow::Owned(key))) => {
+                    return seed
+                        .deserialize(de::value::StrDeserializer::new(&key))
+                        .map(Some);
+                }
+            }
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.0.next_value_seed(seed)
+    }
+}
+
+/// A string borrowed from the input when possible
+///
+/// `#[serde(borrow)]` only borrows a `Cow<str>` that is the whole field, so this is needed for
+/// ones inside an `Option` or used as map keys.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct CowStr<'de>(Cow<'de, str>);
+
+impl<'de> Deserialize<'de> for CowStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = CowStr<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(CowStr(Cow::Borrowed(value)))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(CowStr(Cow::Owned(value.into())))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(CowStr(Cow::Owned(value)))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+/// Deserialize an optional string, borrowing it from the input when possible
+pub(crate) fn option_cow_str<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<CowStr>::deserialize(deserializer)?.map(|s| s.0))
+}
+
+/// Deserialize an optional map, borrowing its keys from the input when possible
+pub(crate) fn option_map_cow_keys<'de: 'a, 'a, D, V>(
+    deserializer: D,
+) -> Result<Option<BTreeMap<Cow<'a, str>, V>>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    let map = Option::<BTreeMap<CowStr, V>>::deserialize(deserializer)?;
+    Ok(map.map(|map| map.into_iter().map(|(key, value)| (key.0, value)).collect()))
+}
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Strip the UTF-8 byte order mark that files saved by some Windows tools start with
+pub(crate) fn strip_bom(json: &[u8]) -> &[u8] {
+    json.strip_prefix(BOM).unwrap_or(json)
+}
+
+/// Strip the UTF-8 byte order mark from a string, see [`strip_bom`]
+pub(crate) fn strip_bom_str(json: &str) -> &str {
+    json.strip_prefix('\u{feff}').unwrap_or(json)
+}
+
+/// Skip the UTF-8 byte order mark at the start of a reader, see [`strip_bom`]
+#[cfg(feature = "std")]
+pub(crate) fn skip_bom<R: Read>(mut reader: R) -> io::Result<impl Read> {
+    let mut start = [0; 3];
+    let mut len = 0;
+    while len < start.len() {
+        match reader.read(&mut start[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    let skip = if &start[..len] == BOM { len } else { 0 };
+    let mut head = io::Cursor::new(start);
+    head.set_position(skip as u64);
+    Ok(head.take((len - skip) as u64).chain(reader))
+}
@@ -0,0 +1,97 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! A common interface for the files the metadata points to, so download code can be generic.
+
+use crate::java_runtime::RuntimeManifest;
+use crate::sha1::Sha1;
+use crate::version::library::Artifact;
+use crate::version::logging::FileInfo;
+use crate::version::{AssetIndex, Download};
+#[cfg(feature = "verify")]
+use crate::VerifyError;
+
+/// A file that can be downloaded, with the size and hash it is expected to have
+///
+/// This is object safe, so different kinds of downloads can be stored as `&dyn Downloadable`.
+pub trait Downloadable {
+    fn sha1(&self) -> &Sha1;
+    fn size(&self) -> u64;
+    fn url(&self) -> &str;
+
+    /// The file name of the download, taken from the last path segment of the URL
+    ///
+    /// Returns `None` if the URL has no path, ends in a slash, or has a query string or fragment.
+    fn file_name(&self) -> Option<&str> {
+        url_file_name(self.url())
+    }
+
+    /// Check that some downloaded data matches the expected size and hash
+    #[cfg(feature = "verify")]
+    fn verify_bytes(&self, bytes: &[u8]) -> bool {
+        self.verify(bytes).is_ok()
+    }
+
+    /// Check that some downloaded data matches the expected size and hash, returning a
+    /// [`VerifyError`] describing the mismatch if it doesn't
+    ///
+    /// The size is checked first, so the hash is only computed for data of the right size.
+    #[cfg(feature = "verify")]
+    fn verify(&self, bytes: &[u8]) -> Result<(), VerifyError> {
+        let actual = bytes.len() as u64;
+        if actual != self.size() {
+            return Err(VerifyError::Size {
+                expected: self.size(),
+                actual,
+            });
+        }
+        let actual = Sha1::digest(bytes);
+        if actual != *self.sha1() {
+            return Err(VerifyError::Hash {
+                expected: *self.sha1(),
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Get the last path segment of a URL, if it unambiguously names a file
+fn url_file_name(url: &str) -> Option<&str> {
+    if url.contains(['?', '#']) {
+        return None;
+    }
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let (_, name) = path.rsplit_once('/')?;
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+macro_rules! impl_downloadable {
+    ($($ty:ty),*) => {
+        $(
+            impl Downloadable for $ty {
+                fn sha1(&self) -> &Sha1 {
+                    &self.sha1
+                }
+
+                fn size(&self) -> u64 {
+                    self.size
+                }
+
+                fn url(&self) -> &str {
+                    &self.url
+                }
+            }
+        )*
+    };
+}
+
+impl_downloadable!(Artifact, AssetIndex, Download, FileInfo, RuntimeManifest);
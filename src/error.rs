@@ -0,0 +1,85 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! The error type shared by the whole crate.
+
+use alloc::vec::Vec;
+// the error trait is only in `core` from Rust 1.81, so the one in `std` is used when available
+#[cfg(not(feature = "std"))]
+pub(crate) use core::error::Error as StdError;
+#[cfg(feature = "std")]
+pub(crate) use std::error::Error as StdError;
+
+use thiserror::Error;
+
+use crate::asset_index::SizeMismatch;
+use crate::sha1::Sha1;
+use crate::version::builder::BuildError;
+use crate::version::library::UnsafePathError;
+use crate::version::maven::MavenCoordParseError;
+use crate::version::merge::MergeError;
+use crate::version::validate::ValidationError;
+
+/// Error returned by the fallible functions of this crate
+///
+/// Functions with a single way to fail return a more specific error, which converts into this, so
+/// `?` can be used to collect all of them into one type.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The input couldn't be read, or wasn't valid JSON for the type
+    #[error("invalid JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+    /// Some data didn't match the size or hash it was expected to have
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+    /// A library name wasn't a valid maven coordinate
+    #[error("invalid maven coordinate: {0}")]
+    Coordinate(#[from] MavenCoordParseError),
+    /// A path from the metadata could escape the directory it is meant to be in
+    #[error("unsafe path: {0}")]
+    UnsafePath(#[from] UnsafePathError),
+    /// A version couldn't be merged with the versions it inherits from
+    #[error("failed to merge versions: {0}")]
+    Merge(#[from] MergeError),
+    /// A version was missing a required field when building it
+    #[error("failed to build version: {0}")]
+    Build(#[from] BuildError),
+    /// A version failed [`Version::validate`](crate::version::Version::validate)
+    #[error("version has {} validation errors", .0.len())]
+    Validation(Vec<ValidationError>),
+}
+
+impl From<Vec<ValidationError>> for Error {
+    fn from(errors: Vec<ValidationError>) -> Self {
+        Error::Validation(errors)
+    }
+}
+
+impl From<SizeMismatch> for Error {
+    fn from(err: SizeMismatch) -> Self {
+        Error::Verify(VerifyError::Size {
+            expected: err.expected,
+            actual: err.actual,
+        })
+    }
+}
+
+/// How some data didn't match what was expected of it
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Error)]
+#[non_exhaustive]
+pub enum VerifyError {
+    #[error("size mismatch: expected {expected} bytes, got {actual}")]
+    Size { expected: u64, actual: u64 },
+    #[error("hash mismatch: expected {expected}, got {actual}")]
+    Hash { expected: Sha1, actual: Sha1 },
+}
+
+/// Error returned by the parsing functions of the `diagnostics` feature, which includes the path
+/// of the field that failed to parse
+#[cfg(feature = "diagnostics")]
+pub type PathError = serde_path_to_error::Error<serde_json::Error>;
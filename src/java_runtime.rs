@@ -0,0 +1,76 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! This module contains the types used by the Java runtime index.
+//!
+//! The index lists the Java runtimes Mojang provides for each platform, keyed by the component
+//! names used in `Version.java_version`, and points to the manifest of files for each runtime.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sha1::Sha1;
+use crate::version::JavaVersion;
+
+/// The current URL to get the Java runtime index from.
+pub const JAVA_RUNTIME_INDEX_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+
+/// The Java runtime index, mapping a platform such as `linux` or `windows-x64` to the runtimes
+/// available for it, keyed by component
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JavaRuntimeIndex(pub BTreeMap<String, BTreeMap<String, Vec<Runtime>>>);
+
+impl JavaRuntimeIndex {
+    /// Get the runtime of a component for a platform
+    ///
+    /// Returns `None` if the platform is unknown, or the component isn't available for it.
+    pub fn get(&self, platform: &str, component: &str) -> Option<&Runtime> {
+        self.0.get(platform)?.get(component)?.first()
+    }
+
+    /// Get the runtime a version needs for a platform
+    pub fn for_java_version(&self, platform: &str, java_version: &JavaVersion) -> Option<&Runtime> {
+        self.get(platform, &java_version.component)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Availability {
+    pub group: u32,
+    pub progress: u8,
+}
+
+/// The manifest listing the files of a runtime
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeManifest {
+    pub sha1: Sha1,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
+    pub size: u64,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuntimeVersion {
+    /// The full Java version, ie `17.0.8`
+    pub name: String,
+    pub released: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Runtime {
+    pub availability: Availability,
+    pub manifest: RuntimeManifest,
+    pub version: RuntimeVersion,
+}
@@ -7,8 +7,8 @@
 
 //! A simple crate that defines the types used by the Minecraft version manifest.
 //!
-//! This crate deliberately does not include any code to actually fetch from the endpoints, as to
-//! not tie it to any particular HTTP client.
+//! By default this crate doesn't fetch from the endpoints itself, so it isn't tied to any
+//! particular HTTP client. The optional `net` feature adds helpers doing so with `reqwest`.
 //!
 //! ## Usage
 //!
@@ -16,8 +16,23 @@
 //! VERSION_MANIFEST_URL, and then fetching the version JSON file from the URL defined in the
 //! Version.url field for the corresponding version.
 //!
-//! No examples are provided, as the exact usage will depend on the HTTP client used to fetch the
-//! manifest and the version JSON files.
+//! With the `net` feature this is done by `Manifest::fetch` and
+//! `version_manifest::Version::fetch_version`. Otherwise the exact usage will depend on the HTTP
+//! client used to fetch the manifest and the version JSON files.
+//!
+//! ## Features
+//!
+//! - `std` (default): adds reading from `std::io` readers, working with file paths, and the other
+//!   APIs that need the standard library. Without it the crate is `no_std` and only needs `alloc`,
+//!   which requires Rust 1.81.
+//! - `chrono`: adds accessors parsing the `time` and `releaseTime` fields into `chrono` types.
+//! - `semver`: allows deduplicating libraries by keeping the highest version.
+//! - `verify`: adds methods checking downloaded data against the expected size and hash.
+//! - `net`: adds async helpers to fetch the metadata using `reqwest`. Implies `verify` and `std`.
+//! - `diagnostics`: adds parsing that reports the path of the field that failed, using
+//!   `serde_path_to_error`. Implies `std`.
+//! - `rayon`: together with `verify` and `std`, adds checking the files of an installed version in
+//!   parallel.
 //!
 //! ## Disclaimer
 //!
@@ -26,21 +41,45 @@
 //! All product and company names are trademarks™ or registered® trademarks of their respective
 //! holders. Use of them does not imply any affiliation with or endorsement by them.
 
-use serde::{Deserialize, Serialize};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub mod asset_index;
+mod de;
+pub mod download;
+mod error;
+pub mod java_runtime;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod sha1;
 pub mod version;
 pub mod version_manifest;
 
+#[cfg(feature = "chrono")]
+pub use chrono;
+#[cfg(feature = "diagnostics")]
+pub use error::PathError;
+pub use error::{Error, VerifyError};
+
 /// The current URL to get the version manifest from.
 pub const VERSION_MANIFEST_URL: &str =
     "https://launchermeta.mojang.com/mc/game/version_manifest.json";
-pub const ASSET_BASE_PATH: &str = 
-    "https://resources.download.minecraft.net/";
+/// The current URL to get the version manifest from, including hashes of the version JSON files.
+pub const VERSION_MANIFEST_V2_URL: &str =
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+pub const ASSET_BASE_PATH: &str = "https://resources.download.minecraft.net/";
+/// The maven repository libraries are downloaded from when they don't specify one.
+pub const LIBRARIES_BASE_URL: &str = "https://libraries.minecraft.net/";
 
 /// Type of Minecraft versions
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum VersionKind {
     Release,
     Snapshot,
@@ -48,4 +87,99 @@ pub enum VersionKind {
     OldAlpha,
     OldSnapshot,
     Experiment,
+    /// A type this crate doesn't know about yet, holding the raw value
+    Other(String),
+}
+
+impl VersionKind {
+    /// The name of this type as it appears in JSON
+    pub fn as_str(&self) -> &str {
+        match self {
+            VersionKind::Release => "release",
+            VersionKind::Snapshot => "snapshot",
+            VersionKind::OldBeta => "old_beta",
+            VersionKind::OldAlpha => "old_alpha",
+            VersionKind::OldSnapshot => "old_snapshot",
+            VersionKind::Experiment => "experiment",
+            VersionKind::Other(other) => other,
+        }
+    }
+
+    /// Whether this is a full release
+    pub fn is_stable(&self) -> bool {
+        matches!(self, VersionKind::Release)
+    }
+
+    /// Whether this is a snapshot, including the snapshots of old versions
+    pub fn is_snapshot(&self) -> bool {
+        matches!(self, VersionKind::Snapshot | VersionKind::OldSnapshot)
+    }
+
+    /// How stable versions of this type are, higher being more stable
+    ///
+    /// Releases rank highest, followed by snapshots, then the old beta and alpha releases.
+    /// Experiments and unknown types rank lowest.
+    pub fn stability_rank(&self) -> u8 {
+        match self {
+            VersionKind::Release => 6,
+            VersionKind::Snapshot => 5,
+            VersionKind::OldSnapshot => 4,
+            VersionKind::OldBeta => 3,
+            VersionKind::OldAlpha => 2,
+            VersionKind::Experiment => 1,
+            VersionKind::Other(_) => 0,
+        }
+    }
+}
+
+impl From<&str> for VersionKind {
+    fn from(s: &str) -> Self {
+        match s {
+            "release" => VersionKind::Release,
+            "snapshot" => VersionKind::Snapshot,
+            "old_beta" => VersionKind::OldBeta,
+            "old_alpha" => VersionKind::OldAlpha,
+            "old_snapshot" => VersionKind::OldSnapshot,
+            "experiment" => VersionKind::Experiment,
+            other => VersionKind::Other(other.to_owned()),
+        }
+    }
+}
+
+impl Serialize for VersionKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Unknown types deserialize into [`VersionKind::Other`] so new types don't break parsing
+impl<'de> Deserialize<'de> for VersionKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(VersionKind::from(s.as_str()))
+    }
+}
+
+/// Parse a timestamp as used in the `time` and `releaseTime` fields
+///
+/// These are usually RFC 3339, with either `Z` or `+00:00` as the offset, but some older files
+/// leave the offset out entirely, in which case UTC is assumed.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_time(s: &str) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+    use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%z") {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+        .map(|time| Utc.from_utc_datetime(&time))
 }
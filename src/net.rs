@@ -0,0 +1,81 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Helpers to fetch the metadata using `reqwest`.
+//!
+//! These are only available with the `net` feature. Like the other constructors, they ignore a
+//! UTF-8 byte order mark at the start of the data.
+
+use thiserror::Error;
+
+use crate::download::Downloadable;
+use crate::sha1::Sha1;
+use crate::version::{AssetIndex, Version};
+use crate::version_manifest::{self, Manifest};
+use crate::{asset_index, VerifyError, VERSION_MANIFEST_V2_URL};
+
+/// Error returned when fetching metadata fails
+#[derive(Debug, Error)]
+pub enum FetchError {
+    /// The request failed, or the server returned an error status
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The downloaded data didn't match the expected size or hash
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
+    /// The downloaded data couldn't be deserialized
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Download the body of a URL, failing on error statuses
+pub(crate) async fn get_bytes(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, FetchError> {
+    let response = client.get(url).send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+impl Manifest {
+    /// Fetch the current version manifest from [`VERSION_MANIFEST_V2_URL`]
+    pub async fn fetch(client: &reqwest::Client) -> Result<Self, FetchError> {
+        let bytes = get_bytes(client, VERSION_MANIFEST_V2_URL).await?;
+        Ok(serde_json::from_slice(crate::de::strip_bom(&bytes))?)
+    }
+}
+
+impl version_manifest::Version {
+    /// Fetch the version JSON file this entry points to
+    ///
+    /// If the entry has a hash, which is the case for entries from the v2 manifest, the file is
+    /// checked against it before being deserialized.
+    pub async fn fetch_version(&self, client: &reqwest::Client) -> Result<Version, FetchError> {
+        let bytes = get_bytes(client, &self.url).await?;
+        if let Some(expected) = self.sha1 {
+            let actual = Sha1::digest(&bytes);
+            if actual != expected {
+                return Err(VerifyError::Hash { expected, actual }.into());
+            }
+        }
+        Ok(serde_json::from_slice(crate::de::strip_bom(&bytes))?)
+    }
+}
+
+impl AssetIndex {
+    /// Fetch the asset index file this points to, containing the asset objects
+    pub async fn fetch(
+        &self,
+        client: &reqwest::Client,
+    ) -> Result<asset_index::AssetIndex, FetchError> {
+        let bytes = get_bytes(client, &self.url).await?;
+        self.parse_objects(&bytes)
+    }
+
+    /// Check a downloaded asset index file against the expected size and hash, then deserialize it
+    pub fn parse_objects(&self, bytes: &[u8]) -> Result<asset_index::AssetIndex, FetchError> {
+        self.verify(bytes)?;
+        Ok(serde_json::from_slice(crate::de::strip_bom(bytes))?)
+    }
+}
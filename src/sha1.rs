@@ -0,0 +1,156 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! A strongly typed SHA1 digest, as used for every `sha1` field in the metadata.
+
+use core::fmt;
+use core::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A SHA1 digest.
+///
+/// In JSON this is always represented as exactly 40 lowercase hex characters. Anything else is
+/// rejected at deserialize time, so a corrupt manifest fails early instead of at download time.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Sha1([u8; 20]);
+
+impl Sha1 {
+    /// Create a digest from its raw bytes
+    pub const fn new(bytes: [u8; 20]) -> Self {
+        Sha1(bytes)
+    }
+
+    /// The raw bytes of the digest
+    pub const fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// Compute the digest of some data
+    #[cfg(feature = "verify")]
+    pub fn digest(data: &[u8]) -> Self {
+        Sha1(sha1_smol::Sha1::from(data).digest().bytes())
+    }
+}
+
+/// Error returned when parsing a [`Sha1`] from a string fails.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Sha1ParseError {
+    /// The string was not exactly 40 characters long
+    InvalidLength(usize),
+    /// The string contained a character that is not a lowercase hex digit
+    InvalidCharacter { index: usize, found: char },
+}
+
+impl fmt::Display for Sha1ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Sha1ParseError::InvalidLength(len) => {
+                write!(f, "expected 40 hex characters, found {}", len)
+            }
+            Sha1ParseError::InvalidCharacter { index, found } => {
+                write!(
+                    f,
+                    "invalid character {:?} at index {}, expected lowercase hex",
+                    found, index
+                )
+            }
+        }
+    }
+}
+
+impl crate::error::StdError for Sha1ParseError {}
+
+fn hex_value(index: usize, c: u8) -> Result<u8, Sha1ParseError> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        _ => {
+            Err(Sha1ParseError::InvalidCharacter {
+                index,
+                found: c as char,
+            })
+        }
+    }
+}
+
+impl FromStr for Sha1 {
+    type Err = Sha1ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // check the characters first so a multibyte char reports a useful error instead of a length
+        if let Some((index, found)) = s
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '0'..='9' | 'a'..='f'))
+        {
+            return Err(Sha1ParseError::InvalidCharacter { index, found });
+        }
+        if s.len() != 40 {
+            return Err(Sha1ParseError::InvalidLength(s.len()));
+        }
+
+        let bytes = s.as_bytes();
+        let mut out = [0u8; 20];
+        for (i, byte) in out.iter_mut().enumerate() {
+            let hi = hex_value(i * 2, bytes[i * 2])?;
+            let lo = hex_value(i * 2 + 1, bytes[i * 2 + 1])?;
+            *byte = (hi << 4) | lo;
+        }
+        Ok(Sha1(out))
+    }
+}
+
+impl fmt::Display for Sha1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Sha1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Sha1({})", self)
+    }
+}
+
+impl Serialize for Sha1 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Sha1 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct Sha1Visitor;
+
+        impl<'de> Visitor<'de> for Sha1Visitor {
+            type Value = Sha1;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string of 40 lowercase hex characters")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Sha1Visitor)
+    }
+}
@@ -0,0 +1,296 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Borrowed variants of the version types, for deserializing without allocating every string.
+//!
+//! The libraries make up the bulk of a version JSON file, with a name, path, and URL for every
+//! one of them. These types borrow those strings from the input where possible, and reuse the
+//! owned types for everything else. Strings containing escape sequences can't be borrowed, so
+//! they fall back to being owned.
+//!
+//! This is an opt-in fast path for tools that parse many version files. Use
+//! [`VersionRef::into_owned`] to convert to the owned types.
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::sha1::Sha1;
+use crate::version::library::{self, Extract, Library, Natives};
+use crate::version::logging::Logging;
+use crate::version::rule::Rule;
+use crate::version::{Arguments, AssetIndex, Downloads, JavaVersion, Version};
+use crate::VersionKind;
+
+/// Borrowed variant of [`library::Artifact`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArtifactRef<'a> {
+    #[serde(borrow)]
+    pub path: Cow<'a, str>,
+    pub sha1: Sha1,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
+    pub size: u64,
+    #[serde(borrow)]
+    pub url: Cow<'a, str>,
+}
+
+/// Borrowed variant of [`library::Downloads`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LibraryDownloadsRef<'a> {
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<ArtifactRef<'a>>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "crate::de::option_map_cow_keys",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub classifiers: Option<BTreeMap<Cow<'a, str>, ArtifactRef<'a>>>,
+}
+
+/// Borrowed variant of [`Library`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LibraryRef<'a> {
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub downloads: Option<LibraryDownloadsRef<'a>>,
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extract: Option<Extract>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub natives: Option<Natives>,
+    #[serde(
+        default,
+        deserialize_with = "crate::de::seq_or_empty_map",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rules: Option<Vec<Rule>>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "crate::de::option_cow_str",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub url: Option<Cow<'a, str>>,
+}
+
+/// Borrowed variant of [`Version`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionRef<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Arguments>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minecraft_arguments: Option<Cow<'a, str>>,
+    pub asset_index: AssetIndex,
+    pub assets: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_version: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance_level: Option<u8>,
+    pub downloads: Downloads,
+    pub id: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherits_from: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub java_version: Option<JavaVersion>,
+    pub libraries: Vec<LibraryRef<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Logging>,
+    pub main_class: Cow<'a, str>,
+    pub minimum_launcher_version: u8,
+    pub release_time: Cow<'a, str>,
+    pub time: Cow<'a, str>,
+    #[serde(rename = "type")]
+    pub kind: VersionKind,
+}
+
+/// The derived deserialization of [`VersionRef`], see the one of [`Version`]
+#[derive(Deserialize)]
+#[serde(remote = "VersionRef", rename_all = "camelCase", deny_unknown_fields)]
+struct VersionRefDef<'a> {
+    #[serde(default)]
+    arguments: Option<Arguments>,
+    #[serde(default, borrow, deserialize_with = "crate::de::option_cow_str")]
+    minecraft_arguments: Option<Cow<'a, str>>,
+    asset_index: AssetIndex,
+    #[serde(borrow)]
+    assets: Cow<'a, str>,
+    #[serde(default, borrow, deserialize_with = "crate::de::option_cow_str")]
+    client_version: Option<Cow<'a, str>>,
+    #[serde(default)]
+    compliance_level: Option<u8>,
+    downloads: Downloads,
+    #[serde(borrow)]
+    id: Cow<'a, str>,
+    #[serde(default, borrow, deserialize_with = "crate::de::option_cow_str")]
+    inherits_from: Option<Cow<'a, str>>,
+    #[serde(default)]
+    java_version: Option<JavaVersion>,
+    #[serde(borrow)]
+    libraries: Vec<LibraryRef<'a>>,
+    #[serde(default)]
+    logging: Option<Logging>,
+    #[serde(borrow)]
+    main_class: Cow<'a, str>,
+    minimum_launcher_version: u8,
+    #[serde(borrow)]
+    release_time: Cow<'a, str>,
+    #[serde(borrow)]
+    time: Cow<'a, str>,
+    #[serde(rename = "type")]
+    kind: VersionKind,
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for VersionRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        VersionRefDef::deserialize(crate::de::SkipUnderscoreKeys(deserializer))
+    }
+}
+
+impl ArtifactRef<'_> {
+    pub fn into_owned(self) -> library::Artifact {
+        library::Artifact {
+            path: self.path.into_owned(),
+            sha1: self.sha1,
+            size: self.size,
+            url: self.url.into_owned(),
+        }
+    }
+}
+
+impl<'a> From<&'a library::Artifact> for ArtifactRef<'a> {
+    fn from(artifact: &'a library::Artifact) -> Self {
+        ArtifactRef {
+            path: Cow::Borrowed(&artifact.path),
+            sha1: artifact.sha1,
+            size: artifact.size,
+            url: Cow::Borrowed(&artifact.url),
+        }
+    }
+}
+
+impl LibraryDownloadsRef<'_> {
+    pub fn into_owned(self) -> library::Downloads {
+        library::Downloads {
+            artifact: self.artifact.map(ArtifactRef::into_owned),
+            classifiers: self.classifiers.map(|classifiers| {
+                classifiers
+                    .into_iter()
+                    .map(|(key, artifact)| (key.into_owned(), artifact.into_owned()))
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl<'a> From<&'a library::Downloads> for LibraryDownloadsRef<'a> {
+    fn from(downloads: &'a library::Downloads) -> Self {
+        LibraryDownloadsRef {
+            artifact: downloads.artifact.as_ref().map(ArtifactRef::from),
+            classifiers: downloads.classifiers.as_ref().map(|classifiers| {
+                classifiers
+                    .iter()
+                    .map(|(key, artifact)| (Cow::Borrowed(key.as_str()), artifact.into()))
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl LibraryRef<'_> {
+    pub fn into_owned(self) -> Library {
+        Library {
+            downloads: self.downloads.map(LibraryDownloadsRef::into_owned),
+            name: self.name.into_owned(),
+            extract: self.extract,
+            natives: self.natives,
+            rules: self.rules,
+            url: self.url.map(Cow::into_owned),
+        }
+    }
+}
+
+impl<'a> From<&'a Library> for LibraryRef<'a> {
+    fn from(library: &'a Library) -> Self {
+        LibraryRef {
+            downloads: library.downloads.as_ref().map(LibraryDownloadsRef::from),
+            name: Cow::Borrowed(&library.name),
+            extract: library.extract.clone(),
+            natives: library.natives.clone(),
+            rules: library.rules.clone(),
+            url: library.url.as_deref().map(Cow::Borrowed),
+        }
+    }
+}
+
+impl VersionRef<'_> {
+    /// Convert into the owned [`Version`], allocating any strings that were borrowed
+    pub fn into_owned(self) -> Version {
+        Version {
+            arguments: self.arguments,
+            minecraft_arguments: self.minecraft_arguments.map(Cow::into_owned),
+            asset_index: self.asset_index,
+            assets: self.assets.into_owned(),
+            client_version: self.client_version.map(Cow::into_owned),
+            compliance_level: self.compliance_level,
+            downloads: self.downloads,
+            id: self.id.into_owned(),
+            inherits_from: self.inherits_from.map(Cow::into_owned),
+            java_version: self.java_version,
+            libraries: self
+                .libraries
+                .into_iter()
+                .map(LibraryRef::into_owned)
+                .collect(),
+            logging: self.logging,
+            main_class: self.main_class.into_owned(),
+            minimum_launcher_version: self.minimum_launcher_version,
+            release_time: self.release_time.into_owned(),
+            time: self.time.into_owned(),
+            kind: self.kind,
+        }
+    }
+}
+
+impl<'a> From<&'a Version> for VersionRef<'a> {
+    fn from(version: &'a Version) -> Self {
+        VersionRef {
+            arguments: version.arguments.clone(),
+            minecraft_arguments: version.minecraft_arguments.as_deref().map(Cow::Borrowed),
+            asset_index: version.asset_index.clone(),
+            assets: Cow::Borrowed(&version.assets),
+            client_version: version.client_version.as_deref().map(Cow::Borrowed),
+            compliance_level: version.compliance_level,
+            downloads: version.downloads.clone(),
+            id: Cow::Borrowed(&version.id),
+            inherits_from: version.inherits_from.as_deref().map(Cow::Borrowed),
+            java_version: version.java_version.clone(),
+            libraries: version.libraries.iter().map(LibraryRef::from).collect(),
+            logging: version.logging.clone(),
+            main_class: Cow::Borrowed(&version.main_class),
+            minimum_launcher_version: version.minimum_launcher_version,
+            release_time: Cow::Borrowed(&version.release_time),
+            time: Cow::Borrowed(&version.time),
+            kind: version.kind.clone(),
+        }
+    }
+}
+
+impl From<VersionRef<'_>> for Version {
+    fn from(version: VersionRef<'_>) -> Self {
+        version.into_owned()
+    }
+}
@@ -0,0 +1,212 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! A builder for constructing a [`Version`] in code, ie for custom profiles.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::version::library::Library;
+use crate::version::logging::Logging;
+use crate::version::{Arguments, AssetIndex, Download, Downloads, JavaVersion, Version};
+use crate::VersionKind;
+
+/// Error returned when building a [`Version`] fails
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BuildError {
+    /// A required field wasn't set, holding the name of the field
+    MissingField(&'static str),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingField(field) => write!(f, "missing required field `{}`", field),
+        }
+    }
+}
+
+impl crate::error::StdError for BuildError {}
+
+/// Builds a [`Version`]
+///
+/// `id`, `main_class`, `asset_index` and the client download are required. Everything else
+/// defaults to empty, with `kind` defaulting to [`VersionKind::Release`] and `assets` defaulting
+/// to the id of the asset index.
+#[derive(Debug, Clone, Default)]
+pub struct VersionBuilder {
+    arguments: Option<Arguments>,
+    minecraft_arguments: Option<String>,
+    asset_index: Option<AssetIndex>,
+    assets: Option<String>,
+    compliance_level: Option<u8>,
+    client: Option<Download>,
+    downloads: Option<Downloads>,
+    id: Option<String>,
+    inherits_from: Option<String>,
+    java_version: Option<JavaVersion>,
+    libraries: Vec<Library>,
+    logging: Option<Logging>,
+    main_class: Option<String>,
+    minimum_launcher_version: u8,
+    release_time: String,
+    time: String,
+    kind: Option<VersionKind>,
+}
+
+impl VersionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the id of the version this one builds on
+    pub fn inherits_from(mut self, inherits_from: impl Into<String>) -> Self {
+        self.inherits_from = Some(inherits_from.into());
+        self
+    }
+
+    pub fn kind(mut self, kind: VersionKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn main_class(mut self, main_class: impl Into<String>) -> Self {
+        self.main_class = Some(main_class.into());
+        self
+    }
+
+    pub fn asset_index(mut self, asset_index: AssetIndex) -> Self {
+        self.asset_index = Some(asset_index);
+        self
+    }
+
+    pub fn assets(mut self, assets: impl Into<String>) -> Self {
+        self.assets = Some(assets.into());
+        self
+    }
+
+    /// Set the client jar download, leaving the other downloads as they are
+    pub fn client_download(mut self, client: Download) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Set all the downloads at once, replacing any client download set before
+    pub fn downloads(mut self, downloads: Downloads) -> Self {
+        self.client = Some(downloads.client.clone());
+        self.downloads = Some(downloads);
+        self
+    }
+
+    pub fn arguments(mut self, arguments: Arguments) -> Self {
+        self.arguments = Some(arguments);
+        self
+    }
+
+    pub fn minecraft_arguments(mut self, minecraft_arguments: impl Into<String>) -> Self {
+        self.minecraft_arguments = Some(minecraft_arguments.into());
+        self
+    }
+
+    /// Add a library after the ones already added
+    pub fn library(mut self, library: Library) -> Self {
+        self.libraries.push(library);
+        self
+    }
+
+    /// Replace all libraries
+    pub fn libraries(mut self, libraries: Vec<Library>) -> Self {
+        self.libraries = libraries;
+        self
+    }
+
+    pub fn java_version(mut self, java_version: JavaVersion) -> Self {
+        self.java_version = Some(java_version);
+        self
+    }
+
+    pub fn logging(mut self, logging: Logging) -> Self {
+        self.logging = Some(logging);
+        self
+    }
+
+    pub fn compliance_level(mut self, compliance_level: u8) -> Self {
+        self.compliance_level = Some(compliance_level);
+        self
+    }
+
+    pub fn minimum_launcher_version(mut self, minimum_launcher_version: u8) -> Self {
+        self.minimum_launcher_version = minimum_launcher_version;
+        self
+    }
+
+    pub fn release_time(mut self, release_time: impl Into<String>) -> Self {
+        self.release_time = release_time.into();
+        self
+    }
+
+    pub fn time(mut self, time: impl Into<String>) -> Self {
+        self.time = time.into();
+        self
+    }
+
+    /// Build the version, checking that all required fields are set
+    pub fn build(self) -> Result<Version, BuildError> {
+        let id = self.id.ok_or(BuildError::MissingField("id"))?;
+        let main_class = self
+            .main_class
+            .ok_or(BuildError::MissingField("main_class"))?;
+        let asset_index = self
+            .asset_index
+            .ok_or(BuildError::MissingField("asset_index"))?;
+        let client = self
+            .client
+            .ok_or(BuildError::MissingField("downloads.client"))?;
+        let downloads = match self.downloads {
+            Some(downloads) => {
+                Downloads {
+                    client,
+                    ..downloads
+                }
+            }
+            None => Downloads::new(client),
+        };
+
+        Ok(Version {
+            arguments: self.arguments,
+            minecraft_arguments: self.minecraft_arguments,
+            assets: self.assets.unwrap_or_else(|| asset_index.id.clone()),
+            asset_index,
+            client_version: None,
+            compliance_level: self.compliance_level,
+            downloads,
+            id,
+            inherits_from: self.inherits_from,
+            java_version: self.java_version,
+            libraries: self.libraries,
+            logging: self.logging,
+            main_class,
+            minimum_launcher_version: self.minimum_launcher_version,
+            release_time: self.release_time,
+            time: self.time,
+            kind: self.kind.unwrap_or(VersionKind::Release),
+        })
+    }
+}
+
+impl Version {
+    /// Start building a version in code
+    pub fn builder() -> VersionBuilder {
+        VersionBuilder::new()
+    }
+}
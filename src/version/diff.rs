@@ -0,0 +1,207 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Comparing two versions, ie to show what changed between snapshots.
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sha1::Sha1;
+use crate::version::library::Library;
+use crate::version::{Argument, Arguments, JavaVersion, Version};
+
+/// A library whose version or main artifact changed, see [`VersionDiff::changed_libraries`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LibraryChange {
+    /// The name of the library in the old version
+    pub old: String,
+    /// The name of the library in the new version
+    pub new: String,
+}
+
+/// A download whose hash changed, see [`VersionDiff::changed_downloads`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DownloadChange {
+    /// The label of the download, as given by [`Downloads::iter`](crate::version::Downloads::iter)
+    pub label: String,
+    /// The hash in the old version, or `None` if it didn't have the download
+    pub old: Option<Sha1>,
+    /// The hash in the new version, or `None` if it doesn't have the download
+    pub new: Option<Sha1>,
+}
+
+/// A change of the required Java version, see [`VersionDiff::java_version`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct JavaVersionChange {
+    pub old: Option<JavaVersion>,
+    pub new: Option<JavaVersion>,
+}
+
+/// What changed from one version to another, see [`Version::diff`]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct VersionDiff {
+    /// Names of libraries only in the new version
+    pub added_libraries: Vec<String>,
+    /// Names of libraries only in the old version
+    pub removed_libraries: Vec<String>,
+    /// Libraries in both versions with a different version or main artifact hash
+    pub changed_libraries: Vec<LibraryChange>,
+    pub changed_downloads: Vec<DownloadChange>,
+    /// The change of `javaVersion`, if it changed
+    pub java_version: Option<JavaVersionChange>,
+    /// Game arguments only in the new version
+    pub added_game_arguments: Vec<Argument>,
+    /// Game arguments only in the old version
+    pub removed_game_arguments: Vec<Argument>,
+    /// JVM arguments only in the new version
+    pub added_jvm_arguments: Vec<Argument>,
+    /// JVM arguments only in the old version
+    pub removed_jvm_arguments: Vec<Argument>,
+}
+
+impl VersionDiff {
+    /// Whether nothing this compares changed
+    pub fn is_empty(&self) -> bool {
+        *self == VersionDiff::default()
+    }
+}
+
+/// The key libraries are matched by, their group, artifact and classifier, falling back to the
+/// whole name for libraries with invalid names
+fn library_key(library: &Library) -> String {
+    match library.coord() {
+        Some(coord) => {
+            match coord.classifier {
+                Some(classifier) => format!("{}:{}:{}", coord.group, coord.artifact, classifier),
+                None => format!("{}:{}", coord.group, coord.artifact),
+            }
+        }
+        None => library.name.clone(),
+    }
+}
+
+fn main_sha1(library: &Library) -> Option<Sha1> {
+    library
+        .downloads
+        .as_ref()
+        .and_then(|downloads| downloads.artifact.as_ref())
+        .map(|artifact| artifact.sha1)
+}
+
+/// The arguments of a version, converting `minecraftArguments` for older versions
+fn arguments_of(version: &Version) -> Arguments {
+    match (&version.arguments, &version.minecraft_arguments) {
+        (Some(arguments), _) => arguments.clone(),
+        (None, Some(legacy)) => Arguments::from_legacy(legacy),
+        (None, None) => Arguments::default(),
+    }
+}
+
+/// Split two lists into the entries only in `new` and the entries only in `old`, counting
+/// repeated entries separately
+fn diff_lists<T: Clone + PartialEq>(old: &[T], new: &[T]) -> (Vec<T>, Vec<T>) {
+    let mut removed = old.to_vec();
+    let mut added = Vec::new();
+    for item in new {
+        match removed.iter().position(|old| old == item) {
+            Some(index) => {
+                removed.remove(index);
+            }
+            None => added.push(item.clone()),
+        }
+    }
+    (added, removed)
+}
+
+impl Version {
+    /// Compare this version to a newer one
+    ///
+    /// Libraries are matched by group, artifact and classifier, so a library that only changed
+    /// version is reported as changed rather than removed and added. Downloads are compared by
+    /// hash, and arguments ignoring order. Versions using `minecraftArguments` are compared as if
+    /// converted with [`Arguments::from_legacy`].
+    pub fn diff(&self, other: &Version) -> VersionDiff {
+        let mut old_libraries: BTreeMap<_, Vec<&Library>> = BTreeMap::new();
+        for library in &self.libraries {
+            old_libraries
+                .entry(library_key(library))
+                .or_default()
+                .push(library);
+        }
+
+        let mut diff = VersionDiff::default();
+        for library in &other.libraries {
+            let old = old_libraries
+                .get_mut(&library_key(library))
+                .and_then(|old| (!old.is_empty()).then(|| old.remove(0)));
+            match old {
+                Some(old) => {
+                    if old.name != library.name || main_sha1(old) != main_sha1(library) {
+                        diff.changed_libraries.push(LibraryChange {
+                            old: old.name.clone(),
+                            new: library.name.clone(),
+                        });
+                    }
+                }
+                None => diff.added_libraries.push(library.name.clone()),
+            }
+        }
+        for library in &self.libraries {
+            let key = library_key(library);
+            if let Some(remaining) = old_libraries.get_mut(&key) {
+                if let Some(index) = remaining
+                    .iter()
+                    .position(|old| core::ptr::eq(*old, library))
+                {
+                    remaining.remove(index);
+                    diff.removed_libraries.push(library.name.clone());
+                }
+            }
+        }
+
+        let old_downloads: BTreeMap<_, _> = self.downloads.iter().collect();
+        let new_downloads: BTreeMap<_, _> = other.downloads.iter().collect();
+        let mut labels: Vec<_> = old_downloads.keys().chain(new_downloads.keys()).collect();
+        labels.sort_unstable();
+        labels.dedup();
+        for label in labels {
+            let old = old_downloads.get(label).map(|download| download.sha1);
+            let new = new_downloads.get(label).map(|download| download.sha1);
+            if old != new {
+                diff.changed_downloads.push(DownloadChange {
+                    label: (*label).to_owned(),
+                    old,
+                    new,
+                });
+            }
+        }
+
+        if self.java_version != other.java_version {
+            diff.java_version = Some(JavaVersionChange {
+                old: self.java_version.clone(),
+                new: other.java_version.clone(),
+            });
+        }
+
+        let old_arguments = arguments_of(self);
+        let new_arguments = arguments_of(other);
+        let (added, removed) = diff_lists(&old_arguments.game, &new_arguments.game);
+        diff.added_game_arguments = added;
+        diff.removed_game_arguments = removed;
+        let (added, removed) = diff_lists(&old_arguments.jvm, &new_arguments.jvm);
+        diff.added_jvm_arguments = added;
+        diff.removed_jvm_arguments = removed;
+
+        diff
+    }
+}
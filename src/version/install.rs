@@ -0,0 +1,126 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Checking the files of an installed version against the hashes in its metadata.
+//!
+//! This is only available with the `std`, `verify` and `rayon` features.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::sha1::Sha1;
+use crate::version::rule::RuleContext;
+use crate::version::Version;
+
+/// The state of a file on disk, see [`VerifyReport`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum FileStatus {
+    /// The file has the expected hash
+    Ok,
+    /// The file doesn't exist
+    Missing,
+    /// The file exists but has a different hash
+    Mismatch,
+    /// The path from the metadata would point outside the libraries directory, so it wasn't read
+    UnsafePath,
+}
+
+/// The result of checking one file, see [`Version::verify_installation`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct VerifyReport {
+    /// The maven coordinate of the library the file belongs to, or `client` for the client jar
+    pub coordinate: String,
+    /// Where the file was expected
+    pub path: PathBuf,
+    pub status: FileStatus,
+    pub expected: Sha1,
+    /// The hash of the file on disk, if it could be read
+    pub actual: Option<Sha1>,
+}
+
+impl VerifyReport {
+    fn check(coordinate: &str, path: PathBuf, expected: Sha1) -> io::Result<Self> {
+        let (status, actual) = match std::fs::read(&path) {
+            Ok(bytes) => {
+                let actual = Sha1::digest(&bytes);
+                let status = if actual == expected {
+                    FileStatus::Ok
+                } else {
+                    FileStatus::Mismatch
+                };
+                (status, Some(actual))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (FileStatus::Missing, None),
+            Err(err) => return Err(err),
+        };
+        Ok(VerifyReport {
+            coordinate: coordinate.to_owned(),
+            path,
+            status,
+            expected,
+            actual,
+        })
+    }
+
+    /// Whether the file is present with the expected hash
+    pub fn is_ok(&self) -> bool {
+        self.status == FileStatus::Ok
+    }
+}
+
+impl Version {
+    /// Check every library, natives jar and the client jar of an installed version
+    ///
+    /// This covers the same libraries as [`Version::artifacts`], expected at their paths within
+    /// `libraries_dir`. The files are hashed in parallel, with one report for each in the order
+    /// of the libraries, followed by the client jar. Errors reading a file other than it not
+    /// existing are returned.
+    pub fn verify_installation(
+        &self,
+        ctx: &RuleContext,
+        libraries_dir: &Path,
+        client_jar: &Path,
+    ) -> io::Result<Vec<VerifyReport>> {
+        let mut files: Vec<_> = self
+            .library_artifacts(ctx)
+            .map(|(library, artifact)| {
+                // unsafe paths are reported with the path as given in the metadata
+                let path = artifact
+                    .safe_relative_path()
+                    .map(|path| libraries_dir.join(path))
+                    .map_err(|_| PathBuf::from(&artifact.path));
+                (library.name.as_str(), path, artifact.sha1)
+            })
+            .collect();
+        files.push((
+            "client",
+            Ok(client_jar.to_owned()),
+            self.downloads.client.sha1,
+        ));
+
+        files
+            .into_par_iter()
+            .map(|(coordinate, path, expected)| {
+                match path {
+                    Ok(path) => VerifyReport::check(coordinate, path, expected),
+                    Err(path) => {
+                        Ok(VerifyReport {
+                            coordinate: coordinate.to_owned(),
+                            path,
+                            status: FileStatus::UnsafePath,
+                            expected,
+                            actual: None,
+                        })
+                    }
+                }
+            })
+            .collect()
+    }
+}
@@ -7,56 +7,609 @@
 
 //! Information about the libraries used by the game
 
-use std::collections::BTreeMap;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+#[cfg(feature = "std")]
+use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-use crate::version::rule::Rule;
+use crate::sha1::Sha1;
+use crate::version::maven::MavenCoord;
+use crate::version::rule::{self, OsName, Rule, RuleContext};
+use crate::LIBRARIES_BASE_URL;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Artifact {
     pub path: String,
-    pub sha1: String,
+    pub sha1: Sha1,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
     pub size: u64,
     pub url: String,
 }
 
+/// Error returned when the path of an artifact could escape the directory it is stored in
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Error)]
+#[non_exhaustive]
+pub enum UnsafePathError {
+    #[error("path is empty")]
+    Empty,
+    #[error("path is absolute")]
+    Absolute,
+    #[error("path contains a `..` component")]
+    ParentDir,
+    /// A component contains a `:`, as in a Windows drive prefix like `C:`
+    #[error("path contains a drive prefix")]
+    Prefix,
+}
+
+impl Artifact {
+    /// The path of the artifact, checked to be safe to join onto a libraries directory
+    ///
+    /// The path comes from the manifest, so it can't be trusted. Absolute paths, `..` components
+    /// and drive prefixes are rejected, as they could point outside the directory. Both `/` and
+    /// `\` are treated as separators, and empty and `.` components are dropped.
+    #[cfg(feature = "std")]
+    pub fn safe_relative_path(&self) -> Result<PathBuf, UnsafePathError> {
+        safe_relative_path(&self.path)
+    }
+}
+
+/// Check a path from the manifest, see [`Artifact::safe_relative_path`]
+#[cfg(feature = "std")]
+pub(crate) fn safe_relative_path(path: &str) -> Result<PathBuf, UnsafePathError> {
+    if path.starts_with(['/', '\\']) {
+        return Err(UnsafePathError::Absolute);
+    }
+    let mut relative = PathBuf::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => return Err(UnsafePathError::ParentDir),
+            component if component.contains(':') => return Err(UnsafePathError::Prefix),
+            component => relative.push(component),
+        }
+    }
+    if relative.as_os_str().is_empty() {
+        return Err(UnsafePathError::Empty);
+    }
+    Ok(relative)
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Downloads {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub artifact: Option<Artifact>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub classifiers: Option<BTreeMap<String, Artifact>>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// The natives classifier of each OS, for libraries using the old natives model
+///
+/// Keys for other platforms are ignored, so new ones appearing doesn't break parsing.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct Natives {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linux: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub osx: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub windows: Option<String>,
 }
 
-pub type Extract = BTreeMap<String, Vec<String>>;
+impl Natives {
+    /// The classifier template for an OS, which may contain `${arch}`
+    ///
+    /// Always `None` for [`OsName::Other`]. See [`resolve_natives_key`].
+    pub fn for_os(&self, os: &OsName) -> Option<&str> {
+        match os {
+            OsName::Linux => self.linux.as_deref(),
+            OsName::Osx => self.osx.as_deref(),
+            OsName::Windows => self.windows.as_deref(),
+            OsName::Other(_) => None,
+        }
+    }
+}
+
+/// Information on how to extract a natives jar
+///
+/// In practice this only ever contains an `exclude` key listing paths that must not be extracted.
+/// Any other keys end up in `other`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Extract {
+    /// Paths in the jar that must not be extracted, see [`Extract::should_exclude`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Keys this crate doesn't know about
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Vec<String>>,
+}
+
+impl Extract {
+    /// Check if an entry of the jar must be skipped when extracting
+    ///
+    /// Exclusions are plain prefixes of the entry path, like the vanilla launcher checks them, so
+    /// `META-INF/` excludes everything in the `META-INF` directory and `META-INF/MANIFEST` also
+    /// excludes `META-INF/MANIFEST.MF`.
+    pub fn should_exclude(&self, entry_path: &str) -> bool {
+        self.exclude
+            .iter()
+            .any(|prefix| entry_path.starts_with(prefix.as_str()))
+    }
+}
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Library {
     /// A list of artifacts to potentially download for the library
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub downloads: Option<Downloads>,
     /// The name of the library, in the format `group:name:version`
     pub name: String,
     /// Information on how to extract the library.
     ///
-    /// This is used for natives, and is a map of the files to extract to the directories to extract
-    #[serde(default)]
+    /// This is used for natives, to list the files that must not be extracted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub extract: Option<Extract>,
     /// Information on natives for the version
     ///
     /// This was used in older versions of the format
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub natives: Option<Natives>,
-    #[serde(default)]
+    /// Rules that must allow the library for it to be used
+    ///
+    /// Some exporters write an empty object instead of leaving this out, which is read as `None`.
+    #[serde(
+        default,
+        deserialize_with = "crate::de::seq_or_empty_map",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub rules: Option<Vec<Rule>>,
+    /// The base URL of the maven repository to download the library from
+    ///
+    /// This is only used by libraries without `downloads`, mostly from mod loaders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Library {
+    /// Create a library with only a name, leaving everything else empty
+    pub fn new(name: impl Into<String>) -> Self {
+        Library {
+            downloads: None,
+            name: name.into(),
+            extract: None,
+            natives: None,
+            rules: None,
+            url: None,
+        }
+    }
+
+    /// Parse the name of the library as a maven coordinate
+    pub fn coord(&self) -> Option<MavenCoord> {
+        MavenCoord::parse(&self.name)
+    }
+
+    /// Check if the rules of this library allow it in the given context
+    pub fn applies_to(&self, ctx: &RuleContext) -> bool {
+        rule::is_allowed(self.rules.as_deref().unwrap_or_default(), ctx)
+    }
+
+    /// Check if the rules of this library allow it on an OS, when nothing else is known
+    ///
+    /// This evaluates the rules against a context with only the OS set. Rules that also check
+    /// the architecture, OS version or a feature don't match such a context, so a library only
+    /// disallowed on a specific architecture or OS version is considered allowed, and one only
+    /// allowed there is considered disallowed. Use [`Library::applies_to`] when more is known.
+    pub fn is_allowed_on(&self, os: OsName) -> bool {
+        self.applies_to(&RuleContext {
+            os_name: Some(os),
+            ..RuleContext::default()
+        })
+    }
+
+    /// Whether this library only provides natives
+    ///
+    /// This is the case for libraries with a `natives-*` classifier, and for libraries using the
+    /// old natives model that have no main artifact.
+    pub fn is_native(&self) -> bool {
+        let has_natives_classifier = self.coord().map_or(false, |coord| {
+            coord
+                .classifier
+                .map_or(false, |classifier| classifier.starts_with("natives-"))
+        });
+        let has_artifact = self
+            .downloads
+            .as_ref()
+            .map_or(false, |downloads| downloads.artifact.is_some());
+        has_natives_classifier || (self.natives.is_some() && !has_artifact)
+    }
+
+    /// Whether this library is only used by the client, such as for rendering, sound or input
+    ///
+    /// Nothing in the metadata marks a library as client-only, so this checks against a list of
+    /// the known client libraries, see [`CLIENT_ONLY_LIBRARIES`].
+    pub fn is_client_only(&self) -> bool {
+        let coord = match self.coord() {
+            Some(coord) => coord,
+            None => return false,
+        };
+        CLIENT_ONLY_LIBRARIES.iter().any(|prefix| {
+            match prefix.split_once(':') {
+                Some((group, artifact)) => coord.group == group && coord.artifact == artifact,
+                None => coord.group == *prefix || coord.group.starts_with(&format!("{}.", prefix)),
+            }
+        })
+    }
+
+    /// Look up an artifact in the `classifiers` of the downloads, ie `natives-windows`
+    ///
+    /// The key is used as is, so `${arch}` must already be resolved unless the classifier is
+    /// stored with it unresolved.
+    pub fn classifier(&self, key: &str) -> Option<&Artifact> {
+        self.downloads.as_ref()?.classifiers.as_ref()?.get(key)
+    }
+
+    /// Every artifact in the `classifiers` of the downloads with its classifier, sorted by
+    /// classifier
+    pub fn classifiers(&self) -> impl Iterator<Item = (&str, &Artifact)> {
+        self.downloads
+            .iter()
+            .flat_map(|downloads| downloads.classifiers.iter().flatten())
+            .map(|(classifier, artifact)| (classifier.as_str(), artifact))
+    }
+
+    /// The natives artifact for the given context, for libraries using the old natives model
+    ///
+    /// This looks up the classifier the `natives` field gives for the OS, with `${arch}`
+    /// resolved, in the classifiers of the downloads. If the downloads have a classifier matching
+    /// [`RuleContext::native_classifier`] exactly, that is preferred. As a last resort the
+    /// template is looked up literally, for classifiers stored with `${arch}` unresolved.
+    /// Libraries without natives, or without natives for the OS, return `None`.
+    pub fn native_artifact(&self, ctx: &RuleContext) -> Option<&Artifact> {
+        let template = self.natives.as_ref()?.for_os(ctx.os_name.as_ref()?)?;
+        self.classifier(&ctx.native_classifier())
+            .or_else(|| self.classifier(&resolve_natives_key(template, ctx.arch_bits())))
+            .or_else(|| self.classifier(template))
+    }
+
+    /// The most specific natives artifact for the given context, for libraries using the old
+    /// natives model
+    ///
+    /// Manifests from before lwjgl 3.3 only have a generic classifier for each OS, without the
+    /// `-arm64` and `-x86` variants [`RuleContext::native_classifier`] gives. This tries every
+    /// classifier in [`native_classifier_chain`] in order, so `natives-windows-arm64` falls back
+    /// to `natives-windows` when the library doesn't have it, then falls back to
+    /// [`Library::native_artifact`].
+    ///
+    /// Libraries whose rules don't allow them in the context have no natives.
+    pub fn best_native(&self, ctx: &RuleContext) -> Option<&Artifact> {
+        if ctx.os_name.is_none() || !self.applies_to(ctx) {
+            return None;
+        }
+        native_classifier_chain(&ctx.native_classifier())
+            .iter()
+            .find_map(|classifier| self.classifier(classifier))
+            .or_else(|| self.native_artifact(ctx))
+    }
+
+    /// Every natives artifact this library contributes in the given context
+    ///
+    /// This covers both natives models. Newer versions have a separate library for each natives
+    /// jar, with a classifier like `natives-linux`, in which case its artifact is returned if the
+    /// classifier is [`RuleContext::native_classifier`], so only the natives built for the
+    /// architecture are used. Older versions list the natives of each OS in the `natives` field,
+    /// see [`Library::native_artifact`].
+    ///
+    /// Libraries whose rules don't allow them in the context have no natives.
+    pub fn natives_for(&self, ctx: &RuleContext) -> Vec<&Artifact> {
+        if ctx.os_name.is_none() || !self.applies_to(ctx) {
+            return Vec::new();
+        }
+        if self.natives.is_some() {
+            return self.native_artifact(ctx).into_iter().collect();
+        }
+
+        let classifier = self.coord().and_then(|coord| coord.classifier);
+        if classifier.as_deref() != Some(ctx.native_classifier().as_str()) {
+            return Vec::new();
+        }
+        self.downloads
+            .as_ref()
+            .and_then(|downloads| downloads.artifact.as_ref())
+            .into_iter()
+            .collect()
+    }
+
+    /// Rewrite the URL of the main artifact and every classifier, such as to point them at a
+    /// mirror, returning how many were rewritten
+    ///
+    /// `f` is called with each URL, and replaces it when it returns `Some`. The maven repository
+    /// in `url` is left alone, see [`Version::rewrite_urls`](crate::version::Version::rewrite_urls)
+    /// to rewrite everything in a version.
+    pub fn rewrite_download_url(&mut self, f: impl Fn(&str) -> Option<String>) -> usize {
+        let downloads = match &mut self.downloads {
+            Some(downloads) => downloads,
+            None => return 0,
+        };
+        let classifiers = downloads
+            .classifiers
+            .iter_mut()
+            .flat_map(|c| c.values_mut());
+        let mut rewritten = 0;
+        for artifact in downloads.artifact.iter_mut().chain(classifiers) {
+            if let Some(url) = f(&artifact.url) {
+                artifact.url = url;
+                rewritten += 1;
+            }
+        }
+        rewritten
+    }
+
+    /// The URL to download the main artifact from
+    ///
+    /// This is the URL given in the downloads. Libraries without `downloads`, like the ones mod
+    /// loaders add, instead have the path derived from the name joined onto `url`, or
+    /// [`LIBRARIES_BASE_URL`] if that isn't set.
+    pub fn resolved_url(&self) -> Option<String> {
+        match &self.downloads {
+            Some(downloads) => downloads.artifact.as_ref().map(|a| a.url.clone()),
+            None => {
+                let base = self.url.as_deref().unwrap_or(LIBRARIES_BASE_URL);
+                let path = self.coord()?.path();
+                if base.ends_with('/') {
+                    Some(format!("{}{}", base, path))
+                } else {
+                    Some(format!("{}/{}", base, path))
+                }
+            }
+        }
+    }
+
+    /// Check if two libraries are for the same artifact, comparing only the coordinate and the
+    /// hash of the main artifact
+    ///
+    /// Unlike `==` this ignores download URLs, so a library from a mirror is the same artifact
+    /// as the one in Mojang's manifest.
+    pub fn same_artifact(&self, other: &Library) -> bool {
+        let sha1 = |library: &Library| {
+            library
+                .downloads
+                .as_ref()
+                .and_then(|downloads| downloads.artifact.as_ref())
+                .map(|artifact| artifact.sha1)
+        };
+        let same_coord = match (self.coord(), other.coord()) {
+            (Some(coord), Some(other_coord)) => coord == other_coord,
+            _ => self.name == other.name,
+        };
+        same_coord && sha1(self) == sha1(other)
+    }
+
+    /// The path of the main artifact relative to the libraries directory
+    ///
+    /// This is the path given in the downloads, falling back to the path derived from the name.
+    pub fn artifact_path(&self) -> Option<String> {
+        match self.downloads.as_ref().and_then(|d| d.artifact.as_ref()) {
+            Some(artifact) => Some(artifact.path.clone()),
+            None => self.coord().map(|coord| coord.path()),
+        }
+    }
+}
+
+/// Libraries used only by the client, as either a group, which includes its subgroups, or a
+/// `group:artifact` pair
+pub const CLIENT_ONLY_LIBRARIES: &[&str] = &[
+    "ca.weblite",
+    "com.mojang:realms",
+    "com.mojang:text2speech",
+    "com.paulscode",
+    "net.java.jinput",
+    "net.java.jutils",
+    "org.joml",
+    "org.lwjgl",
+    "tv.twitch",
+];
+
+/// Natives classifiers that older manifests don't have, each with the classifier to use instead
+///
+/// lwjgl 3.3 added separate natives for arm64 and 32 bit x86, and lwjgl 3 renamed `natives-osx`
+/// to `natives-macos`.
+pub const NATIVE_CLASSIFIER_FALLBACKS: &[(&str, &str)] = &[
+    ("natives-windows-arm64", "natives-windows"),
+    ("natives-windows-x86", "natives-windows"),
+    ("natives-macos-arm64", "natives-macos"),
+    ("natives-macos", "natives-osx"),
+    ("natives-linux-arm64", "natives-linux"),
+    ("natives-linux-arm32", "natives-linux"),
+];
+
+/// The natives classifiers to try for a classifier, from most to least specific
+///
+/// This starts with the classifier itself and follows [`NATIVE_CLASSIFIER_FALLBACKS`], so
+/// `natives-macos-arm64` gives `natives-macos-arm64`, `natives-macos` and `natives-osx`.
+pub fn native_classifier_chain(classifier: &str) -> Vec<&str> {
+    let mut chain = vec![classifier];
+    while let Some((_, fallback)) = NATIVE_CLASSIFIER_FALLBACKS
+        .iter()
+        .find(|(specific, _)| Some(specific) == chain.last())
+    {
+        chain.push(fallback);
+    }
+    chain
+}
+
+/// Resolve the `${arch}` placeholder in a natives classifier, ie `natives-windows-${arch}`
+///
+/// `arch_bits` should be `32` or `64`.
+pub fn resolve_natives_key(template: &str, arch_bits: u8) -> String {
+    template.replace("${arch}", &arch_bits.to_string())
+}
+
+/// How [`dedup_libraries_by`] picks which of several versions of a library to keep
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DedupPolicy {
+    /// Keep the last occurrence, which is what Minecraft expects after merging a child version
+    /// onto its parent
+    LastWins,
+    /// Keep the highest version, comparing them as semver. Versions that aren't valid semver
+    /// fall back to last wins.
+    #[cfg(feature = "semver")]
+    HighestVersion,
+}
+
+impl Default for DedupPolicy {
+    fn default() -> Self {
+        DedupPolicy::LastWins
+    }
+}
+
+/// Remove duplicate libraries, keeping the last occurrence of each
+///
+/// See [`dedup_libraries_by`].
+pub fn dedup_libraries(libs: &[Library]) -> Vec<Library> {
+    dedup_libraries_by(libs, DedupPolicy::LastWins)
+}
+
+/// Remove duplicate libraries, choosing which one to keep with the given policy
+///
+/// Libraries are duplicates if they have the same group, artifact and classifier, so the natives
+/// of a library are not considered duplicates of the library itself. Libraries with names that
+/// aren't maven coordinates are always kept. The kept libraries stay in their original order.
+pub fn dedup_libraries_by(libs: &[Library], policy: DedupPolicy) -> Vec<Library> {
+    let coords: Vec<_> = libs.iter().map(Library::coord).collect();
+    let mut winners: BTreeMap<_, (usize, &MavenCoord)> = BTreeMap::new();
+    for (index, coord) in coords.iter().enumerate() {
+        let coord = match coord {
+            Some(coord) => coord,
+            None => continue,
+        };
+        let key = (&coord.group, &coord.artifact, &coord.classifier);
+        let replace = match winners.get(&key) {
+            Some((_, current)) => policy.prefers(coord, current),
+            None => true,
+        };
+        if replace {
+            winners.insert(key, (index, coord));
+        }
+    }
+
+    libs.iter()
+        .zip(&coords)
+        .enumerate()
+        .filter(|(index, (_, coord))| {
+            match coord {
+                Some(coord) => {
+                    winners[&(&coord.group, &coord.artifact, &coord.classifier)].0 == *index
+                }
+                None => true,
+            }
+        })
+        .map(|(_, (library, _))| library.clone())
+        .collect()
+}
+
+impl DedupPolicy {
+    /// Whether a later occurrence of a library should replace the current one
+    #[cfg_attr(not(feature = "semver"), allow(unused_variables))]
+    fn prefers(self, later: &MavenCoord, current: &MavenCoord) -> bool {
+        match self {
+            DedupPolicy::LastWins => true,
+            #[cfg(feature = "semver")]
+            DedupPolicy::HighestVersion => {
+                match (
+                    semver::Version::parse(&later.version),
+                    semver::Version::parse(&current.version),
+                ) {
+                    (Ok(later), Ok(current)) => later >= current,
+                    _ => true,
+                }
+            }
+        }
+    }
+}
+
+/// An owned list of libraries, with helpers for filtering and looking them up
+///
+/// The order of the libraries is kept, as it is the order they go on the classpath.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LibrarySet(pub Vec<Library>);
+
+impl LibrarySet {
+    pub fn iter(&self) -> core::slice::Iter<'_, Library> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The libraries whose rules allow them in the given context
+    pub fn applicable(&self, ctx: &RuleContext) -> LibrarySet {
+        self.iter()
+            .filter(|library| library.applies_to(ctx))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove duplicate libraries, keeping the last occurrence of each, see [`dedup_libraries`]
+    pub fn dedup(&self) -> LibrarySet {
+        LibrarySet(dedup_libraries(&self.0))
+    }
+
+    /// Every library with the given group and artifact, whatever its version and classifier
+    ///
+    /// Libraries with names that aren't maven coordinates never match.
+    pub fn by_coordinate<'a>(
+        &'a self,
+        group: &'a str,
+        artifact: &'a str,
+    ) -> impl Iterator<Item = &'a Library> {
+        self.iter().filter(move |library| {
+            library.coord().map_or(false, |coord| {
+                coord.group == group && coord.artifact == artifact
+            })
+        })
+    }
+}
+
+impl From<Vec<Library>> for LibrarySet {
+    fn from(libraries: Vec<Library>) -> Self {
+        LibrarySet(libraries)
+    }
+}
+
+impl FromIterator<Library> for LibrarySet {
+    fn from_iter<I: IntoIterator<Item = Library>>(iter: I) -> Self {
+        LibrarySet(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for LibrarySet {
+    type IntoIter = alloc::vec::IntoIter<Library>;
+    type Item = Library;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a LibrarySet {
+    type IntoIter = core::slice::Iter<'a, Library>;
+    type Item = &'a Library;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
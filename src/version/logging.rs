@@ -10,28 +10,117 @@
 //!
 //! I am unsure how this is used.
 
-use serde::{Deserialize, Serialize};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::sha1::Sha1;
+use crate::version::Side;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct FileInfo {
     pub id: String,
-    pub sha1: String,
+    pub sha1: Sha1,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
     pub size: u64,
     pub url: String,
 }
 
+/// The format of a logging configuration file
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum LoggingType {
+    /// A log4j2 XML configuration, the only type used so far
+    Log4j2Xml,
+    /// A type this crate doesn't know about yet, holding the raw value
+    Other(String),
+}
+
+impl LoggingType {
+    /// The name of this type as it appears in JSON
+    pub fn as_str(&self) -> &str {
+        match self {
+            LoggingType::Log4j2Xml => "log4j2-xml",
+            LoggingType::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for LoggingType {
+    fn from(s: &str) -> Self {
+        match s {
+            "log4j2-xml" => LoggingType::Log4j2Xml,
+            other => LoggingType::Other(other.to_owned()),
+        }
+    }
+}
+
+impl Serialize for LoggingType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Unknown types deserialize into [`LoggingType::Other`] so new types don't break parsing
+impl<'de> Deserialize<'de> for LoggingType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(LoggingType::from(s.as_str()))
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Entry {
     pub argument: String,
     pub file: FileInfo,
     #[serde(rename = "type")]
-    pub kind: String,
+    pub kind: LoggingType,
+}
+
+impl Entry {
+    pub fn new(argument: impl Into<String>, file: FileInfo, kind: LoggingType) -> Self {
+        Entry {
+            argument: argument.into(),
+            file,
+            kind,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Logging {
     pub client: Entry,
+    /// The logging config for the server, which vanilla versions don't have yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<Entry>,
+}
+
+impl Logging {
+    /// Create a logging config for the client only
+    pub fn new(client: Entry) -> Self {
+        Logging {
+            client,
+            server: None,
+        }
+    }
+
+    /// The logging config for a side, if there is one
+    pub fn for_side(&self, side: Side) -> Option<&Entry> {
+        match side {
+            Side::Client => Some(&self.client),
+            Side::Server => self.server.as_ref(),
+        }
+    }
 }
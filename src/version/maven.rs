@@ -0,0 +1,139 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Maven coordinates, which is the format library names are specified in
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+
+/// A parsed maven coordinate, in the format `group:artifact:version[:classifier][@extension]`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MavenCoord {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+    pub classifier: Option<String>,
+    /// The file extension, if it isn't `jar`
+    pub extension: Option<String>,
+}
+
+/// Error returned when parsing a [`MavenCoord`] from a string fails.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MavenCoordParseError {
+    /// A required part was missing, holding the name of the part
+    MissingPart(&'static str),
+    /// A part was present but empty, holding the name of the part
+    EmptyPart(&'static str),
+    /// There were more than five `:` separated parts
+    TooManyParts,
+}
+
+impl fmt::Display for MavenCoordParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MavenCoordParseError::MissingPart(part) => write!(f, "missing {}", part),
+            MavenCoordParseError::EmptyPart(part) => write!(f, "{} is empty", part),
+            MavenCoordParseError::TooManyParts => f.write_str("too many parts"),
+        }
+    }
+}
+
+impl crate::error::StdError for MavenCoordParseError {}
+
+impl FromStr for MavenCoord {
+    type Err = MavenCoordParseError;
+
+    /// Parse a coordinate in the format `group:artifact:version[:classifier][@extension]`
+    ///
+    /// The extension can also be given as a fifth part, as in
+    /// `group:artifact:version:classifier:extension`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (coord, extension) = match s.split_once('@') {
+            Some((coord, extension)) => (coord, Some(extension)),
+            None => (s, None),
+        };
+        let mut parts = coord.split(':');
+        let mut next = |name| {
+            match parts.next() {
+                Some("") => Err(MavenCoordParseError::EmptyPart(name)),
+                part => Ok(part),
+            }
+        };
+        let group = next("group")?.ok_or(MavenCoordParseError::MissingPart("group"))?;
+        let artifact = next("artifact")?.ok_or(MavenCoordParseError::MissingPart("artifact"))?;
+        let version = next("version")?.ok_or(MavenCoordParseError::MissingPart("version"))?;
+        let classifier = next("classifier")?;
+        let extension = match (extension, next("extension")?) {
+            (Some(_), Some(_)) => return Err(MavenCoordParseError::TooManyParts),
+            (Some(""), None) => return Err(MavenCoordParseError::EmptyPart("extension")),
+            (extension, None) | (None, extension) => extension,
+        };
+        if parts.next().is_some() {
+            return Err(MavenCoordParseError::TooManyParts);
+        }
+
+        Ok(MavenCoord {
+            group: group.to_owned(),
+            artifact: artifact.to_owned(),
+            version: version.to_owned(),
+            classifier: classifier.map(str::to_owned),
+            extension: extension.map(str::to_owned),
+        })
+    }
+}
+
+/// Formats as `group:artifact:version[:classifier][@extension]`, which parses back to the same
+/// coordinate
+impl fmt::Display for MavenCoord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.group, self.artifact, self.version)?;
+        if let Some(classifier) = &self.classifier {
+            write!(f, ":{}", classifier)?;
+        }
+        if let Some(extension) = &self.extension {
+            write!(f, "@{}", extension)?;
+        }
+        Ok(())
+    }
+}
+
+impl MavenCoord {
+    /// Parse a coordinate, returning `None` if it isn't valid
+    ///
+    /// See the [`FromStr`] implementation for the accepted formats.
+    pub fn parse(s: &str) -> Option<Self> {
+        s.parse().ok()
+    }
+
+    /// The file extension, defaulting to `jar`
+    pub fn extension(&self) -> &str {
+        self.extension.as_deref().unwrap_or("jar")
+    }
+
+    /// The path of the file within a maven repository, ie
+    /// `com/google/guava/guava/32.1.2-jre/guava-32.1.2-jre.jar`
+    pub fn path(&self) -> String {
+        let mut path = format!(
+            "{}/{}/{}/{}-{}",
+            self.group.replace('.', "/"),
+            self.artifact,
+            self.version,
+            self.artifact,
+            self.version
+        );
+        if let Some(classifier) = &self.classifier {
+            path.push('-');
+            path.push_str(classifier);
+        }
+        path.push('.');
+        path.push_str(self.extension());
+        path
+    }
+}
@@ -0,0 +1,324 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Merging versions that use `inheritsFrom` onto the versions they build on.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::version::library::Library;
+use crate::version::logging::Logging;
+use crate::version::{Arguments, AssetIndex, Downloads, JavaVersion, Version};
+use crate::VersionKind;
+
+/// The most versions an inheritance chain may have, counting the one it starts from
+pub const MAX_INHERITANCE_DEPTH: usize = 16;
+
+/// Error returned when resolving an inheritance chain fails
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Error)]
+#[non_exhaustive]
+pub enum MergeError {
+    /// A version inherits from itself, directly or through others, holding the ids of the chain
+    /// up to and including the repeated one
+    #[error("inheritance cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+    /// The chain has more than [`MAX_INHERITANCE_DEPTH`] versions, holding the ids visited
+    #[error("inheritance chain is too deep: {}", .0.join(" -> "))]
+    TooDeep(Vec<String>),
+    /// The loader didn't find a parent, holding its id
+    #[error("parent version `{0}` not found")]
+    MissingParent(String),
+    /// The version the chain ends at doesn't inherit from anything but leaves out a field,
+    /// holding its id and the key of the field
+    #[error("root version `{0}` is missing `{1}`")]
+    Incomplete(String, &'static str),
+}
+
+/// Which of a child version and its parent comes first when merging their libraries and
+/// arguments, see [`PartialVersion::merge_onto`]
+///
+/// Loaders disagree on this, and the order of the classpath decides which of two versions of a
+/// class is loaded. The same order is used for both the game and the JVM arguments.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MergePolicy {
+    /// The child's libraries and arguments come before the parent's, so its libraries win on the
+    /// classpath
+    ChildFirst,
+    /// The parent's libraries and arguments come before the child's, so the child's libraries
+    /// win when deduplicating with [`DedupPolicy::LastWins`]
+    ///
+    /// [`DedupPolicy::LastWins`]: crate::version::library::DedupPolicy::LastWins
+    ParentFirst,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy::ChildFirst
+    }
+}
+
+/// Concatenate the lists of a child and its parent in the order of the policy
+fn merge_lists<T>(policy: MergePolicy, parent: Vec<T>, child: Vec<T>) -> Vec<T> {
+    let (mut first, second) = match policy {
+        MergePolicy::ChildFirst => (child, parent),
+        MergePolicy::ParentFirst => (parent, child),
+    };
+    first.extend(second);
+    first
+}
+
+/// A version JSON file that inherits from another, as mod loaders write them
+///
+/// Loaders usually only set what they add or change, such as `mainClass`, `libraries` and
+/// `arguments`, so everything but the id is optional here and taken from the parent when merging.
+/// A [`Version`] converts into this without losing anything. Unknown keys are rejected like for
+/// [`Version`].
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PartialVersion {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Arguments>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minecraft_arguments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_index: Option<AssetIndex>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compliance_level: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub downloads: Option<Downloads>,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherits_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub java_version: Option<JavaVersion>,
+    pub libraries: Vec<Library>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Logging>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main_class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_launcher_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<VersionKind>,
+}
+
+/// The derived deserialization of [`PartialVersion`], see the one of [`Version`]
+#[derive(Deserialize)]
+#[serde(
+    remote = "PartialVersion",
+    rename_all = "camelCase",
+    deny_unknown_fields
+)]
+struct PartialVersionDef {
+    #[serde(default)]
+    arguments: Option<Arguments>,
+    #[serde(default)]
+    minecraft_arguments: Option<String>,
+    #[serde(default)]
+    asset_index: Option<AssetIndex>,
+    #[serde(default)]
+    assets: Option<String>,
+    #[serde(default)]
+    client_version: Option<String>,
+    #[serde(default)]
+    compliance_level: Option<u8>,
+    #[serde(default)]
+    downloads: Option<Downloads>,
+    id: String,
+    #[serde(default)]
+    inherits_from: Option<String>,
+    #[serde(default)]
+    java_version: Option<JavaVersion>,
+    #[serde(default)]
+    libraries: Vec<Library>,
+    #[serde(default)]
+    logging: Option<Logging>,
+    #[serde(default)]
+    main_class: Option<String>,
+    #[serde(default)]
+    minimum_launcher_version: Option<u8>,
+    #[serde(default)]
+    release_time: Option<String>,
+    #[serde(default)]
+    time: Option<String>,
+    #[serde(default, rename = "type")]
+    kind: Option<VersionKind>,
+}
+
+impl<'de> Deserialize<'de> for PartialVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        PartialVersionDef::deserialize(crate::de::SkipUnderscoreKeys(deserializer))
+    }
+}
+
+impl From<Version> for PartialVersion {
+    fn from(version: Version) -> Self {
+        PartialVersion {
+            arguments: version.arguments,
+            minecraft_arguments: version.minecraft_arguments,
+            asset_index: Some(version.asset_index),
+            assets: Some(version.assets),
+            client_version: version.client_version,
+            compliance_level: version.compliance_level,
+            downloads: Some(version.downloads),
+            id: version.id,
+            inherits_from: version.inherits_from,
+            java_version: version.java_version,
+            libraries: version.libraries,
+            logging: version.logging,
+            main_class: Some(version.main_class),
+            minimum_launcher_version: Some(version.minimum_launcher_version),
+            release_time: Some(version.release_time),
+            time: Some(version.time),
+            kind: Some(version.kind),
+        }
+    }
+}
+
+impl PartialVersion {
+    /// Merge this version onto the version it inherits from
+    ///
+    /// Libraries and the `arguments` lists are this version's and the parent's, in the order
+    /// `policy` gives. Fields this version leaves out are taken from the parent. The result
+    /// doesn't inherit from anything.
+    pub fn merge_onto(self, parent: Version, policy: MergePolicy) -> Version {
+        let arguments = match (parent.arguments, self.arguments) {
+            (Some(parent), Some(child)) => {
+                Some(Arguments {
+                    game: merge_lists(policy, parent.game, child.game),
+                    jvm: merge_lists(policy, parent.jvm, child.jvm),
+                })
+            }
+            (parent, child) => child.or(parent),
+        };
+        let libraries = merge_lists(policy, parent.libraries, self.libraries);
+
+        Version {
+            arguments,
+            minecraft_arguments: self.minecraft_arguments.or(parent.minecraft_arguments),
+            asset_index: self.asset_index.unwrap_or(parent.asset_index),
+            assets: self.assets.unwrap_or(parent.assets),
+            client_version: self.client_version.or(parent.client_version),
+            compliance_level: self.compliance_level.or(parent.compliance_level),
+            downloads: self.downloads.unwrap_or(parent.downloads),
+            id: self.id,
+            inherits_from: None,
+            java_version: self.java_version.or(parent.java_version),
+            libraries,
+            logging: self.logging.or(parent.logging),
+            main_class: self.main_class.unwrap_or(parent.main_class),
+            minimum_launcher_version: self
+                .minimum_launcher_version
+                .unwrap_or(parent.minimum_launcher_version),
+            release_time: self.release_time.unwrap_or(parent.release_time),
+            time: self.time.unwrap_or(parent.time),
+            kind: self.kind.unwrap_or(parent.kind),
+        }
+    }
+
+    /// Load every version this one inherits from and merge them all, starting from the root
+    ///
+    /// `loader` is called with the id of each parent, and each version is merged onto its parent
+    /// with [`PartialVersion::merge_onto`] using `policy`. Cycles, chains longer than
+    /// [`MAX_INHERITANCE_DEPTH`], parents the loader can't find and a root that is missing fields
+    /// are errors.
+    pub fn resolve_chain(
+        &self,
+        policy: MergePolicy,
+        mut loader: impl FnMut(&str) -> Option<PartialVersion>,
+    ) -> Result<Version, MergeError> {
+        let mut ids = vec![self.id.clone()];
+        let mut chain = vec![self.clone()];
+        let mut parent_id = self.inherits_from.clone();
+        while let Some(id) = parent_id {
+            if ids.contains(&id) {
+                ids.push(id);
+                return Err(MergeError::Cycle(ids));
+            }
+            if chain.len() == MAX_INHERITANCE_DEPTH {
+                return Err(MergeError::TooDeep(ids));
+            }
+            let parent = loader(&id).ok_or_else(|| MergeError::MissingParent(id.clone()))?;
+            parent_id = parent.inherits_from.clone();
+            ids.push(id);
+            chain.push(parent);
+        }
+
+        let mut merged = chain
+            .pop()
+            .expect("the chain contains at least this version")
+            .into_root()?;
+        while let Some(child) = chain.pop() {
+            merged = child.merge_onto(merged, policy);
+        }
+        Ok(merged)
+    }
+
+    /// Convert the version an inheritance chain ends at into a complete version
+    fn into_root(self) -> Result<Version, MergeError> {
+        let id = self.id;
+        let missing = |field| MergeError::Incomplete(id.clone(), field);
+        Ok(Version {
+            arguments: self.arguments,
+            minecraft_arguments: self.minecraft_arguments,
+            asset_index: self.asset_index.ok_or_else(|| missing("assetIndex"))?,
+            assets: self.assets.ok_or_else(|| missing("assets"))?,
+            client_version: self.client_version,
+            compliance_level: self.compliance_level,
+            downloads: self.downloads.ok_or_else(|| missing("downloads"))?,
+            inherits_from: None,
+            java_version: self.java_version,
+            libraries: self.libraries,
+            logging: self.logging,
+            main_class: self.main_class.ok_or_else(|| missing("mainClass"))?,
+            minimum_launcher_version: self
+                .minimum_launcher_version
+                .ok_or_else(|| missing("minimumLauncherVersion"))?,
+            release_time: self.release_time.ok_or_else(|| missing("releaseTime"))?,
+            time: self.time.ok_or_else(|| missing("time"))?,
+            kind: self.kind.ok_or_else(|| missing("type"))?,
+            id,
+        })
+    }
+}
+
+impl Version {
+    /// Merge this version onto the version it inherits from, see [`PartialVersion::merge_onto`]
+    pub fn merge_onto(self, parent: Version, policy: MergePolicy) -> Version {
+        PartialVersion::from(self).merge_onto(parent, policy)
+    }
+
+    /// Load every version this one inherits from and merge them all, see
+    /// [`PartialVersion::resolve_chain`]
+    ///
+    /// A version that doesn't inherit from anything is returned as is. Use
+    /// [`PartialVersion::resolve_chain`] if the parents may leave out fields themselves.
+    pub fn resolve_chain(
+        &self,
+        policy: MergePolicy,
+        mut loader: impl FnMut(&str) -> Option<Version>,
+    ) -> Result<Version, MergeError> {
+        PartialVersion::from(self.clone())
+            .resolve_chain(policy, |id| loader(id).map(PartialVersion::from))
+    }
+}
@@ -10,31 +10,166 @@
 //! These files specify info about how to run the game, and are fetched from the URLs specified in
 //! the version manifest.
 
+pub mod borrowed;
+pub mod builder;
+pub mod diff;
+#[cfg(all(feature = "std", feature = "verify", feature = "rayon"))]
+pub mod install;
 pub mod library;
 pub mod logging;
+pub mod maven;
+pub mod merge;
 pub mod rule;
+#[cfg(feature = "std")]
+pub mod stream;
+pub mod validate;
 
-use std::fmt;
-use std::str::FromStr;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::io::Read;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
 
-use library::Library;
+use library::{Artifact, Library, LibrarySet};
 use logging::Logging;
-use rule::Rule;
+use rule::{Features, Os, OsName, Rule, RuleAction, RuleContext};
 use serde::de::{Error, MapAccess, SeqAccess, Visitor};
-use serde::{de, Deserialize, Deserializer, Serialize};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
 
+use crate::asset_index::AssetLayout;
+use crate::sha1::Sha1;
 use crate::VersionKind;
 
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct Argument {
     pub rules: Vec<Rule>,
-    #[serde(rename="value")]
     pub values: Vec<String>,
+    /// Whether a single value is written as an array rather than a bare string
+    ///
+    /// Mojang uses both shapes, so this is kept to serialize an argument the same way it was
+    /// read. It has no effect on arguments with more than one value.
+    pub array_value: bool,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
-struct ArrayOrStringHelper(pub Vec<String>);
+struct ArrayOrStringHelper {
+    values: Vec<String>,
+    is_array: bool,
+}
+
+/// An element of an argument value array, holding the kind of value if it isn't a string
+enum ArrayElement {
+    String(String),
+    Other(&'static str),
+}
+
+impl<'de> Deserialize<'de> for ArrayElement {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ElementVisitor;
+
+        impl<'de> Visitor<'de> for ElementVisitor {
+            type Value = ArrayElement;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("any value")
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArrayElement::String(s.to_owned()))
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArrayElement::Other("boolean"))
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArrayElement::Other("number"))
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArrayElement::Other("number"))
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArrayElement::Other("number"))
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ArrayElement::Other("null"))
+            }
+
+            fn visit_seq<S>(self, seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: SeqAccess<'de>,
+            {
+                de::IgnoredAny.visit_seq(seq)?;
+                Ok(ArrayElement::Other("array"))
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                de::IgnoredAny.visit_map(map)?;
+                Ok(ArrayElement::Other("object"))
+            }
+        }
+
+        deserializer.deserialize_any(ElementVisitor)
+    }
+}
+
+/// Read an array of argument values, with an error naming the index of the first element that
+/// isn't a string
+fn string_elements<'de, S>(mut seq: S) -> Result<Vec<String>, S::Error>
+where
+    S: SeqAccess<'de>,
+{
+    let mut values = Vec::new();
+    while let Some(element) = seq.next_element()? {
+        match element {
+            ArrayElement::String(value) => values.push(value),
+            ArrayElement::Other(kind) => {
+                return Err(S::Error::custom(format_args!(
+                    "argument value array must contain only strings, found {} at index {}",
+                    kind,
+                    values.len()
+                )));
+            }
+        }
+    }
+    Ok(values)
+}
 
 /// deserialize either an array of strings or a single string into always a vector of strings
 impl<'de> Deserialize<'de> for ArrayOrStringHelper {
@@ -55,18 +190,20 @@ impl<'de> Deserialize<'de> for ArrayOrStringHelper {
             where
                 E: de::Error,
             {
-                Ok(ArrayOrStringHelper(vec![s.to_owned()]))
+                Ok(ArrayOrStringHelper {
+                    values: vec![s.to_owned()],
+                    is_array: false,
+                })
             }
 
-            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+            fn visit_seq<S>(self, seq: S) -> Result<Self::Value, S::Error>
             where
                 S: SeqAccess<'de>,
             {
-                let mut vec = Vec::new();
-                while let Some(elem) = seq.next_element::<String>()? {
-                    vec.push(elem);
-                }
-                Ok(ArrayOrStringHelper(vec))
+                Ok(ArrayOrStringHelper {
+                    values: string_elements(seq)?,
+                    is_array: true,
+                })
             }
         }
 
@@ -74,17 +211,116 @@ impl<'de> Deserialize<'de> for ArrayOrStringHelper {
     }
 }
 
+/// Serializes a JSON value with the keys of every object sorted, whatever order the map keeps
+///
+/// Maps of `serde_json` values are only sorted without its `preserve_order` feature, which any
+/// other crate in the dependency graph can enable.
+struct SortedKeys<'a>(&'a serde_json::Value);
+
+impl Serialize for SortedKeys<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by_key(|(key, _)| *key);
+                let mut state = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    state.serialize_entry(key, &SortedKeys(value))?;
+                }
+                state.end()
+            }
+            serde_json::Value::Array(values) => {
+                serializer.collect_seq(values.iter().map(SortedKeys))
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+/// Error returned when parsing an [`Argument`] from a string fails.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ArgumentParseError {
+    /// The string was empty or only contained whitespace
+    Empty,
+}
+
+impl fmt::Display for ArgumentParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgumentParseError::Empty => f.write_str("argument is empty"),
+        }
+    }
+}
+
+impl crate::error::StdError for ArgumentParseError {}
+
+/// serialize a vector of strings as a single string if it has exactly one element and wasn't
+/// read from an array, or an array otherwise, matching how Mojang writes argument values
+struct ArrayOrStringRef<'a> {
+    values: &'a [String],
+    is_array: bool,
+}
+
+impl Serialize for ArrayOrStringRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.values {
+            [value] if !self.is_array => serializer.serialize_str(value),
+            values => values.serialize(serializer),
+        }
+    }
+}
+
 impl FromStr for Argument {
-    type Err = ();
+    type Err = ArgumentParseError;
 
+    /// Parse a single unconditional argument
+    ///
+    /// Whitespace-only strings are rejected as empty, as they would vanish on the command line.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err(ArgumentParseError::Empty);
+        }
         Ok(Argument {
             rules: vec![],
             values: vec![s.to_owned()],
+            array_value: false,
         })
     }
 }
 
+/// Serialize in the same shape Mojang uses, so the output can be read back by a launcher
+///
+/// An unconditional single value is written as a bare string, anything else as an object with
+/// `rules` and `value` fields, where `value` is a bare string if there is only one and
+/// [`Argument::array_value`] isn't set. Unconditional values read from a nested array are written
+/// as an object with empty `rules`, which reads back the same.
+impl Serialize for Argument {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match (self.rules.as_slice(), self.values.as_slice()) {
+            ([], [value]) => serializer.serialize_str(value),
+            (rules, values) => {
+                let mut state = serializer.serialize_struct("Argument", 2)?;
+                state.serialize_field("rules", rules)?;
+                let value = ArrayOrStringRef {
+                    values,
+                    is_array: self.array_value,
+                };
+                state.serialize_field("value", &value)?;
+                state.end()
+            }
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for Argument {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -96,7 +332,7 @@ impl<'de> Deserialize<'de> for Argument {
             type Value = Argument;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("string or object with rules and value fields")
+                formatter.write_str("string, array of strings, or object with a value field")
             }
 
             fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
@@ -106,6 +342,19 @@ impl<'de> Deserialize<'de> for Argument {
                 Ok(Argument {
                     rules: vec![],
                     values: vec![s.to_owned()],
+                    array_value: false,
+                })
+            }
+
+            // a few snapshots group unconditional values in a nested array
+            fn visit_seq<S>(self, seq: S) -> Result<Self::Value, S::Error>
+            where
+                S: SeqAccess<'de>,
+            {
+                Ok(Argument {
+                    rules: vec![],
+                    values: string_elements(seq)?,
+                    array_value: true,
                 })
             }
 
@@ -124,11 +373,12 @@ impl<'de> Deserialize<'de> for Argument {
                             }
                             rules = Some(map.next_value()?);
                         }
-                        "value" => {
+                        // some tools write the values under `values`, after the field name
+                        "value" | "values" => {
                             if value.is_some() {
                                 return Err(de::Error::duplicate_field("value"));
                             }
-                            value = Some(map.next_value::<ArrayOrStringHelper>()?.0);
+                            value = Some(map.next_value::<ArrayOrStringHelper>()?);
                         }
                         _ => {
                             return Err(Error::unknown_field(&key, &["rules", "value"]));
@@ -136,12 +386,14 @@ impl<'de> Deserialize<'de> for Argument {
                     }
                 }
 
-                let rules = rules.ok_or_else(|| de::Error::missing_field("rules"))?;
+                // third party manifests sometimes leave out the rules of unconditional arguments
+                let rules = rules.unwrap_or_default();
                 let value = value.ok_or_else(|| de::Error::missing_field("value"))?;
 
                 Ok(Argument {
                     rules,
-                    values: value,
+                    values: value.values,
+                    array_value: value.is_array,
                 })
             }
         }
@@ -150,70 +402,529 @@ impl<'de> Deserialize<'de> for Argument {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+impl Argument {
+    /// An unconditional argument with a single value
+    ///
+    /// Unlike parsing with [`FromStr`], this doesn't reject empty values.
+    pub fn literal(value: impl Into<String>) -> Self {
+        Argument {
+            rules: vec![],
+            values: vec![value.into()],
+            array_value: false,
+        }
+    }
+
+    /// An argument with the given values that only applies when the rules allow it
+    pub fn conditional<I>(values: I, rules: Vec<Rule>) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Argument {
+            rules,
+            values: values.into_iter().map(Into::into).collect(),
+            array_value: false,
+        }
+    }
+
+    /// Check if the rules of this argument allow it in the given context
+    pub fn applies_to(&self, ctx: &RuleContext) -> bool {
+        rule::is_allowed(&self.rules, ctx)
+    }
+}
+
+/// Flatten the values of every argument that applies in the given context
+fn build_arguments(arguments: &[Argument], ctx: &RuleContext) -> Vec<String> {
+    arguments
+        .iter()
+        .filter(|argument| argument.applies_to(ctx))
+        .flat_map(|argument| argument.values.iter().cloned())
+        .collect()
+}
+
+/// Flags the JVM accepts several times, each with a different value
+const REPEATABLE_FLAGS: &[&str] = &[
+    "--add-exports",
+    "--add-modules",
+    "--add-opens",
+    "--add-reads",
+];
+
+/// Find flags that appear more than once in a list of tokens
+///
+/// A flag is a token starting with `-`, named by everything before the first `=`, so
+/// `-Dfoo=a` and `-Dfoo=b` are duplicates of each other.
+fn check_duplicates(tokens: &[String]) -> Vec<ArgWarning> {
+    let mut seen = BTreeMap::new();
+    let mut warnings = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if !token.starts_with('-') || token.len() == 1 {
+            continue;
+        }
+        let flag = token.split('=').next().unwrap_or(token);
+        if REPEATABLE_FLAGS.contains(&flag) {
+            continue;
+        }
+        if let Some(&first) = seen.get(flag) {
+            warnings.push(ArgWarning::DuplicateFlag {
+                flag: flag.to_owned(),
+                first,
+                second: index,
+            });
+        } else {
+            seen.insert(flag, index);
+        }
+    }
+    warnings
+}
+
+/// The names of every `${...}` placeholder in an argument
+pub(crate) fn placeholders(argument: &str) -> impl Iterator<Item = &str> {
+    argument.split("${").skip(1).filter_map(|rest| {
+        let end = rest.find('}')?;
+        Some(&rest[..end])
+    })
+}
+
+/// The game and JVM arguments of a version
+///
+/// Either list may be left out, as in versions that inherit from another and only add JVM
+/// arguments, in which case it is empty. Both are always written when serializing, as vanilla
+/// versions always have both.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Arguments {
+    #[serde(default)]
     pub game: Vec<Argument>,
+    #[serde(default)]
     pub jvm: Vec<Argument>,
 }
 
+impl Arguments {
+    /// Add a game argument after the others, ie for a mod loader to add its own
+    pub fn push_game(&mut self, argument: Argument) {
+        self.game.push(argument);
+    }
+
+    /// Add a JVM argument after the others, ie `-Dfml.ignorePatchDiscrepancies=true`
+    pub fn push_jvm(&mut self, argument: Argument) {
+        self.jvm.push(argument);
+    }
+
+    /// Add several game arguments after the others
+    pub fn extend_game(&mut self, arguments: impl IntoIterator<Item = Argument>) {
+        self.game.extend(arguments);
+    }
+
+    /// Add several JVM arguments after the others
+    pub fn extend_jvm(&mut self, arguments: impl IntoIterator<Item = Argument>) {
+        self.jvm.extend(arguments);
+    }
+
+    /// Build the game arguments that apply in the given context, without substituting placeholders
+    pub fn build_game(&self, ctx: &RuleContext) -> Vec<String> {
+        build_arguments(&self.game, ctx)
+    }
+
+    /// Build the JVM arguments that apply in the given context, without substituting placeholders
+    pub fn build_jvm(&self, ctx: &RuleContext) -> Vec<String> {
+        build_arguments(&self.jvm, ctx)
+    }
+
+    /// Like [`Arguments::build_game`], also returning warnings about flags that appear more than
+    /// once
+    pub fn build_game_checked(&self, ctx: &RuleContext) -> (Vec<String>, Vec<ArgWarning>) {
+        let tokens = self.build_game(ctx);
+        let warnings = check_duplicates(&tokens);
+        (tokens, warnings)
+    }
+
+    /// Like [`Arguments::build_jvm`], also returning warnings about flags that appear more than
+    /// once
+    ///
+    /// Duplicates usually mean arguments were merged badly, ie from a version and the one it
+    /// inherits from. Flags that are meant to be repeated, like `--add-opens`, are not reported.
+    pub fn build_jvm_checked(&self, ctx: &RuleContext) -> (Vec<String>, Vec<ArgWarning>) {
+        let tokens = self.build_jvm(ctx);
+        let warnings = check_duplicates(&tokens);
+        (tokens, warnings)
+    }
+
+    /// Convert the `minecraftArguments` string of an older version
+    ///
+    /// Every whitespace separated token becomes an unconditional game argument, and the JVM
+    /// arguments are [`Arguments::legacy_jvm`].
+    pub fn from_legacy(minecraft_arguments: &str) -> Self {
+        Arguments {
+            game: minecraft_arguments
+                .split_whitespace()
+                .map(Argument::literal)
+                .collect(),
+            jvm: Arguments::legacy_jvm(),
+        }
+    }
+
+    /// Flatten the game arguments into a `minecraftArguments` string, for exporting to launchers
+    /// that predate the `arguments` field
+    ///
+    /// The game arguments are built for the OS and architecture of the context with every
+    /// feature disabled, as `minecraftArguments` can't express optional arguments, and joined
+    /// with spaces. The JVM arguments are left out, as legacy launchers supply their own.
+    pub fn to_legacy_string(&self, ctx: &RuleContext) -> String {
+        let ctx = RuleContext {
+            features: Features::default(),
+            ..ctx.clone()
+        };
+        self.build_game(&ctx).join(" ")
+    }
+
+    /// The JVM arguments the vanilla launcher uses for versions that predate the `arguments` field
+    pub fn legacy_jvm() -> Vec<Argument> {
+        let os_rule = |name| {
+            Rule {
+                action: RuleAction::Allow,
+                os: Some(Os {
+                    name: Some(name),
+                    version: None,
+                    arch: None,
+                }),
+                features: Features::default(),
+            }
+        };
+        let mut windows_10 = os_rule(OsName::Windows);
+        if let Some(os) = &mut windows_10.os {
+            os.version = Some("^10\\.".to_owned());
+        }
+        vec![
+            Argument {
+                rules: vec![os_rule(OsName::Osx)],
+                values: vec!["-XstartOnFirstThread".to_owned()],
+                array_value: true,
+            },
+            Argument {
+                rules: vec![os_rule(OsName::Windows)],
+                values: vec![
+                    "-XX:HeapDumpPath=MojangTricksIntelDriversForPerformance_javaw.exe_minecraft.\
+                     exe.heapdump"
+                        .to_owned(),
+                ],
+                array_value: false,
+            },
+            Argument {
+                rules: vec![windows_10],
+                values: vec![
+                    "-Dos.name=Windows 10".to_owned(),
+                    "-Dos.version=10.0".to_owned(),
+                ],
+                array_value: true,
+            },
+            Argument::literal("-Djava.library.path=${natives_directory}"),
+            Argument::literal("-Dminecraft.launcher.brand=${launcher_name}"),
+            Argument::literal("-Dminecraft.launcher.version=${launcher_version}"),
+            Argument::literal("-cp"),
+            Argument::literal("${classpath}"),
+        ]
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct AssetIndex {
     pub id: String,
-    pub sha1: String,
+    pub sha1: Sha1,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
     pub size: u64,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
     pub total_size: u64,
     pub url: String,
 }
 
+impl AssetIndex {
+    /// The index file as a plain [`Download`], leaving out the id and the total size of the
+    /// objects
+    ///
+    /// The index is already [`Downloadable`](crate::download::Downloadable) itself, this is for
+    /// code that wants to store it alongside the other downloads.
+    pub fn download(&self) -> Download {
+        Download {
+            sha1: self.sha1,
+            size: self.size,
+            url: self.url.clone(),
+        }
+    }
+}
+
+impl From<&AssetIndex> for Download {
+    fn from(asset_index: &AssetIndex) -> Self {
+        asset_index.download()
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Download {
-    pub sha1: String,
+    pub sha1: Sha1,
+    #[serde(deserialize_with = "crate::de::u64_or_string")]
     pub size: u64,
     pub url: String,
 }
 
+/// The jars and mappings of a version
+///
+/// Downloads under keys this crate doesn't know about, such as sides added after it was
+/// released, end up in `extra` rather than failing to parse.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Downloads {
     pub client: Download,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub client_mappings: Option<Download>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub server: Option<Download>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub server_mappings: Option<Download>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub windows_server: Option<Download>,
+    /// Downloads under any other key, see [`Downloads::extra_sides`]
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Download>,
+}
+
+impl Downloads {
+    /// Create the downloads of a version with only a client jar
+    pub fn new(client: Download) -> Self {
+        Downloads {
+            client,
+            client_mappings: None,
+            server: None,
+            server_mappings: None,
+            windows_server: None,
+            extra: BTreeMap::new(),
+        }
+    }
+
+    /// Iterate over every present download, labelled with the key it is stored under in the JSON
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Download)> {
+        core::iter::once(("client", Some(&self.client)))
+            .chain([
+                ("client_mappings", self.client_mappings.as_ref()),
+                ("server", self.server.as_ref()),
+                ("server_mappings", self.server_mappings.as_ref()),
+                ("windows_server", self.windows_server.as_ref()),
+            ])
+            .filter_map(|(label, download)| download.map(|download| (label, download)))
+    }
+
+    /// Iterate over the downloads under keys this crate doesn't know about, labelled with their
+    /// key and sorted by it
+    ///
+    /// These aren't included in [`Downloads::iter`].
+    pub fn extra_sides(&self) -> impl Iterator<Item = (&str, &Download)> {
+        self.extra
+            .iter()
+            .map(|(label, download)| (label.as_str(), download))
+    }
+
+    /// Iterate over the present obfuscation mappings downloads, labelled with the side they are
+    /// for
+    pub fn mappings(&self) -> impl Iterator<Item = (Side, &Download)> {
+        [
+            (Side::Client, self.client_mappings.as_ref()),
+            (Side::Server, self.server_mappings.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(side, download)| download.map(|download| (side, download)))
+    }
+}
+
+/// Which side of the game something is for
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Side {
+    Client,
+    Server,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct JavaVersion {
     pub component: String,
-    pub major_version: u8,
+    /// The major Java version, ie `17`, also accepted as a string
+    #[serde(deserialize_with = "crate::de::u16_or_string")]
+    pub major_version: u16,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+impl JavaVersion {
+    pub fn new(component: impl Into<String>, major_version: u16) -> Self {
+        JavaVersion {
+            component: component.into(),
+            major_version,
+        }
+    }
+
+    /// Java 8, used by every version before 21w19a, and assumed for versions without a
+    /// `javaVersion` field
+    pub fn jre_legacy() -> Self {
+        Self::new("jre-legacy", 8)
+    }
+
+    /// Java 16, used from 21w19a until 1.18 pre-release 1
+    pub fn java_runtime_alpha() -> Self {
+        Self::new("java-runtime-alpha", 16)
+    }
+
+    /// Java 17, used from 1.18 pre-release 2 until 22w17a
+    pub fn java_runtime_beta() -> Self {
+        Self::new("java-runtime-beta", 17)
+    }
+
+    /// Java 17, used from 22w18a until 24w13a
+    pub fn java_runtime_gamma() -> Self {
+        Self::new("java-runtime-gamma", 17)
+    }
+
+    /// Java 21, used from 24w14a onwards
+    pub fn java_runtime_delta() -> Self {
+        Self::new("java-runtime-delta", 21)
+    }
+}
+
+/// The difference between the game arguments of `arguments` and `minecraftArguments`, see
+/// [`Version::check_argument_parity`]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct ArgumentDiff {
+    /// Tokens only in `arguments`
+    pub added: Vec<String>,
+    /// Tokens only in `minecraftArguments`
+    pub removed: Vec<String>,
+}
+
+impl fmt::Display for ArgumentDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "game arguments differ, added {:?}, removed {:?}",
+            self.added, self.removed
+        )
+    }
+}
+
+impl crate::error::StdError for ArgumentDiff {}
+
+/// A short summary of a version, see [`Version::summary`]
+#[derive(Debug, Clone, Copy)]
+pub struct Summary<'a>(&'a Version);
+
+impl fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let version = self.0;
+        writeln!(f, "{} ({})", version.id, version.kind.as_str())?;
+        writeln!(f, "released:   {}", version.release_time)?;
+        match &version.java_version {
+            Some(java) => writeln!(f, "java:       {} ({})", java.component, java.major_version)?,
+            None => writeln!(f, "java:       jre-legacy (8, assumed)")?,
+        }
+        writeln!(f, "libraries:  {}", version.libraries.len())?;
+        write!(f, "main class: {}", version.main_class)
+    }
+}
+
+/// A suspicious argument found by [`Arguments::build_jvm_checked`] or
+/// [`Arguments::build_game_checked`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ArgWarning {
+    /// A flag appeared more than once, with the indices of the first and the repeated token
+    DuplicateFlag {
+        flag: String,
+        first: usize,
+        second: usize,
+    },
+}
+
+impl fmt::Display for ArgWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgWarning::DuplicateFlag {
+                flag,
+                first,
+                second,
+            } => {
+                write!(
+                    f,
+                    "`{}` appears at both argument {} and argument {}",
+                    flag, first, second
+                )
+            }
+        }
+    }
+}
+
+/// The era of the version JSON format a version was written in, see [`Version::format_version`]
+///
+/// Later eras compare greater than earlier ones.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum ManifestFormat {
+    /// Uses `minecraftArguments`, without a `logging` config
+    PreLogging,
+    /// Uses `minecraftArguments`, with a `logging` config
+    Legacy,
+    /// Uses `arguments`, without specifying the Java version
+    Arguments,
+    /// Uses `arguments` and specifies the Java version in `javaVersion`
+    JavaVersion,
+    /// Also has a `complianceLevel`, as every version since 1.14.4 does
+    ComplianceLevel,
+}
+
+/// The newest `minimumLauncherVersion` this crate knows how to fully interpret
+///
+/// See [`Version::is_supported`].
+pub const SUPPORTED_LAUNCHER_VERSION: u8 = 21;
+
+/// The main classes of vanilla versions, see [`Version::is_vanilla_main_class`]
+///
+/// These are the main class since 1.6, the launchwrapper used by alpha and beta versions, and the
+/// classes the classic and pre-classic versions start from.
+pub const VANILLA_MAIN_CLASSES: &[&str] = &[
+    "net.minecraft.client.main.Main",
+    "net.minecraft.launchwrapper.Launch",
+    "com.mojang.minecraft.Minecraft",
+    "com.mojang.rubydung.RubyDung",
+];
+
+/// A version JSON file
+///
+/// Unknown keys are rejected, except for keys starting with `_`, which some tools add as
+/// comments. Those are ignored and not written back.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Version {
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Arguments>,
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub minecraft_arguments: Option<String>,
     pub asset_index: AssetIndex,
     pub assets: String,
-    #[serde(default)]
+    /// The game version a modded version is for, which some launchers add, see
+    /// [`Version::client_version_hint`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub compliance_level: Option<u8>,
     pub downloads: Downloads,
     pub id: String,
-    #[serde(default)]
+    /// The id of the version this one builds on, used by mod loaders
+    ///
+    /// See [`Version::resolve_chain`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherits_from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub java_version: Option<JavaVersion>,
     pub libraries: Vec<Library>,
-    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub logging: Option<Logging>,
     pub main_class: String,
     pub minimum_launcher_version: u8,
@@ -222,3 +933,608 @@ pub struct Version {
     #[serde(rename = "type")]
     pub kind: VersionKind,
 }
+
+/// The derived deserialization of [`Version`], which its implementation runs with
+/// [`SkipUnderscoreKeys`](crate::de::SkipUnderscoreKeys)
+#[derive(Deserialize)]
+#[serde(remote = "Version", rename_all = "camelCase", deny_unknown_fields)]
+struct VersionDef {
+    #[serde(default)]
+    arguments: Option<Arguments>,
+    #[serde(default)]
+    minecraft_arguments: Option<String>,
+    asset_index: AssetIndex,
+    assets: String,
+    #[serde(default)]
+    client_version: Option<String>,
+    #[serde(default)]
+    compliance_level: Option<u8>,
+    downloads: Downloads,
+    id: String,
+    #[serde(default)]
+    inherits_from: Option<String>,
+    #[serde(default)]
+    java_version: Option<JavaVersion>,
+    libraries: Vec<Library>,
+    #[serde(default)]
+    logging: Option<Logging>,
+    main_class: String,
+    minimum_launcher_version: u8,
+    release_time: String,
+    time: String,
+    #[serde(rename = "type")]
+    kind: VersionKind,
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        VersionDef::deserialize(crate::de::SkipUnderscoreKeys(deserializer))
+    }
+}
+
+impl Version {
+    /// Read a version from JSON
+    ///
+    /// Like the other constructors, this ignores a UTF-8 byte order mark at the start and
+    /// whitespace at the end, which files that went through Windows tools often have.
+    /// Deserialize with `serde_json` directly to reject those.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, crate::Error> {
+        let reader = crate::de::skip_bom(reader).map_err(serde_json::Error::io)?;
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Parse a version from JSON bytes
+    pub fn from_slice(json: &[u8]) -> Result<Self, crate::Error> {
+        Ok(serde_json::from_slice(crate::de::strip_bom(json))?)
+    }
+
+    /// Parse a version from a JSON string
+    pub fn from_str_json(json: &str) -> Result<Self, crate::Error> {
+        Ok(serde_json::from_str(crate::de::strip_bom_str(json))?)
+    }
+
+    /// Write the version as compact JSON, with keys in the same order as Mojang's files
+    ///
+    /// Mojang sorts the keys of every object, so writing them in that order rather than the order
+    /// of the struct fields keeps diffs against the official files minimal.
+    pub fn to_json(&self) -> Result<String, crate::Error> {
+        let value = serde_json::to_value(self)?;
+        Ok(serde_json::to_string(&SortedKeys(&value))?)
+    }
+
+    /// Write the version as JSON indented by four spaces like Mojang's files, with keys in the
+    /// same order, see [`Version::to_json`]
+    #[cfg(feature = "std")]
+    pub fn to_json_pretty(&self) -> Result<String, crate::Error> {
+        let value = serde_json::to_value(self)?;
+        let mut json = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut json, formatter);
+        SortedKeys(&value).serialize(&mut serializer)?;
+        Ok(String::from_utf8(json).expect("serde_json writes valid UTF-8"))
+    }
+
+    /// Read only the arguments of a version from JSON, without parsing the rest of it
+    ///
+    /// The other fields are skipped over rather than deserialized, which is much faster for tools
+    /// that only build command lines. Versions that predate the `arguments` field have their
+    /// `minecraftArguments` converted with [`Arguments::from_legacy`]. Returns `None` if the
+    /// version has neither.
+    ///
+    /// Unlike the other constructors this doesn't check that the rest of the version is valid.
+    pub fn arguments_only(json: &str) -> Result<Option<Arguments>, crate::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ArgumentsOnly<'a> {
+            #[serde(default, borrow)]
+            arguments: Option<&'a RawValue>,
+            #[serde(default, borrow)]
+            minecraft_arguments: Option<Cow<'a, str>>,
+        }
+
+        let only: ArgumentsOnly = serde_json::from_str(crate::de::strip_bom_str(json))?;
+        match (only.arguments, only.minecraft_arguments) {
+            (Some(arguments), _) => Ok(serde_json::from_str(arguments.get())?),
+            (None, Some(legacy)) => Ok(Some(Arguments::from_legacy(&legacy))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// The client jar download
+    pub fn client_download(&self) -> &Download {
+        &self.downloads.client
+    }
+
+    /// The server jar download, which very old versions don't have
+    pub fn server_download(&self) -> Option<&Download> {
+        self.downloads.server.as_ref()
+    }
+
+    /// The jar download for a side
+    pub fn download_for(&self, side: Side) -> Option<&Download> {
+        match side {
+            Side::Client => Some(self.client_download()),
+            Side::Server => self.server_download(),
+        }
+    }
+
+    /// Whether this version meets the current safety requirements, such as Microsoft account
+    /// support, as indicated by a `complianceLevel` of at least 1
+    ///
+    /// Versions without a compliance level are treated as level 0. Launchers use this to warn
+    /// before playing old versions.
+    pub fn is_compliant(&self) -> bool {
+        self.compliance_level.unwrap_or(0) >= 1
+    }
+
+    /// Whether this version needs a launcher supporting at least the given format level, from
+    /// `minimumLauncherVersion`
+    pub fn requires_launcher_at_least(&self, level: u8) -> bool {
+        self.minimum_launcher_version >= level
+    }
+
+    /// Whether this crate can fully interpret this version, ie it doesn't need a launcher newer
+    /// than [`SUPPORTED_LAUNCHER_VERSION`]
+    ///
+    /// Unsupported versions still parse, but may use fields or rules in ways this crate doesn't
+    /// know about, so tools should warn rather than rely on everything being handled.
+    pub fn is_supported(&self) -> bool {
+        self.is_supported_by(SUPPORTED_LAUNCHER_VERSION)
+    }
+
+    /// Whether this version doesn't need a launcher newer than the given format level
+    pub fn is_supported_by(&self, level: u8) -> bool {
+        self.minimum_launcher_version <= level
+    }
+
+    /// The game version this is for, if the file has a `clientVersion` hint
+    ///
+    /// Launchers like MultiMC and Prism add this to modded versions. It isn't checked against
+    /// `inheritsFrom` or anything else.
+    pub fn client_version_hint(&self) -> Option<&str> {
+        self.client_version.as_deref()
+    }
+
+    /// The class the game is started from
+    pub fn main_class(&self) -> &str {
+        &self.main_class
+    }
+
+    /// Whether the main class is one vanilla versions use, from [`VANILLA_MAIN_CLASSES`]
+    ///
+    /// Mod loaders replace the main class, so this is also `false` for modded versions. Tools
+    /// running manifests they don't trust can use this to warn before launching an unexpected
+    /// entry point.
+    pub fn is_vanilla_main_class(&self) -> bool {
+        VANILLA_MAIN_CLASSES.contains(&self.main_class())
+    }
+
+    /// Whether this version only has the old `minecraftArguments` string rather than the
+    /// `arguments` field
+    pub fn uses_legacy_arguments(&self) -> bool {
+        self.arguments.is_none() && self.minecraft_arguments.is_some()
+    }
+
+    /// Check that the game arguments from `arguments` and `minecraftArguments` agree, for
+    /// versions that have both
+    ///
+    /// `arguments` is built with no features enabled, and tokens are compared ignoring order. If
+    /// the version only has one of the fields this always succeeds.
+    pub fn check_argument_parity(&self) -> Result<(), ArgumentDiff> {
+        let (arguments, legacy) = match (&self.arguments, &self.minecraft_arguments) {
+            (Some(arguments), Some(legacy)) => (arguments, legacy),
+            _ => return Ok(()),
+        };
+
+        let mut removed: Vec<_> = legacy.split_whitespace().collect();
+        let mut added = Vec::new();
+        for token in arguments.build_game(&RuleContext::default()) {
+            match removed.iter().position(|legacy| *legacy == token) {
+                Some(index) => {
+                    removed.remove(index);
+                }
+                None => added.push(token),
+            }
+        }
+
+        if added.is_empty() && removed.is_empty() {
+            Ok(())
+        } else {
+            Err(ArgumentDiff {
+                added,
+                removed: removed.into_iter().map(str::to_owned).collect(),
+            })
+        }
+    }
+
+    /// Classify which era of the format this version was written in, from the fields present
+    pub fn format_version(&self) -> ManifestFormat {
+        if self.arguments.is_none() {
+            if self.logging.is_some() {
+                ManifestFormat::Legacy
+            } else {
+                ManifestFormat::PreLogging
+            }
+        } else if self.compliance_level.is_some() {
+            ManifestFormat::ComplianceLevel
+        } else if self.java_version.is_some() {
+            ManifestFormat::JavaVersion
+        } else {
+            ManifestFormat::Arguments
+        }
+    }
+
+    /// Parse a version from a JSON string, with errors including the path of the field that
+    /// failed, ie `libraries[23].downloads.artifact.size`
+    ///
+    /// Like [`Version::from_str_json`] this ignores a UTF-8 byte order mark at the start.
+    #[cfg(feature = "diagnostics")]
+    pub fn from_str_diagnostic(json: &str) -> Result<Self, crate::PathError> {
+        let json = crate::de::strip_bom_str(json);
+        let deserializer = &mut serde_json::Deserializer::from_str(json);
+        serde_path_to_error::deserialize(deserializer)
+    }
+
+    /// Parse `release_time`, the time the version was first released
+    #[cfg(feature = "chrono")]
+    pub fn released_at(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::parse_time(&self.release_time)
+    }
+
+    /// Parse `time`, the time the version was last updated
+    #[cfg(feature = "chrono")]
+    pub fn updated_at(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::parse_time(&self.time)
+    }
+
+    /// A copy of the libraries of this version, for filtering and looking them up
+    pub fn library_set(&self) -> LibrarySet {
+        LibrarySet(self.libraries.clone())
+    }
+
+    /// The id of the assets this version uses
+    ///
+    /// This is `assets`, falling back to the id of the asset index if that is empty, as in some
+    /// third party files.
+    pub fn assets_id(&self) -> &str {
+        if self.assets.is_empty() {
+            &self.asset_index.id
+        } else {
+            &self.assets
+        }
+    }
+
+    /// How the assets of this version are laid out on disk, see [`Version::assets_id`]
+    pub fn asset_layout(&self) -> AssetLayout {
+        AssetLayout::from_assets_id(self.assets_id())
+    }
+
+    /// The Java version needed to run this version
+    ///
+    /// Older version files don't specify this, in which case Java 8 is assumed.
+    pub fn required_java(&self) -> JavaVersion {
+        self.java_version
+            .clone()
+            .unwrap_or_else(JavaVersion::jre_legacy)
+    }
+
+    /// The Java runtime component needed to run this version, ie `java-runtime-gamma`
+    ///
+    /// Like [`Version::required_java`], this is `jre-legacy` for versions that don't specify it.
+    pub fn java_component_str(&self) -> &str {
+        self.java_version
+            .as_ref()
+            .map_or("jre-legacy", |java| java.component.as_str())
+    }
+
+    /// The component and platform to look up in Mojang's Java runtime index to download the Java
+    /// this version needs, ie `("java-runtime-gamma", "mac-os-arm64")`
+    ///
+    /// See [`RuleContext::java_platform_key`] for the platform.
+    pub fn java_download_selector(&self, ctx: &RuleContext) -> (String, &'static str) {
+        (
+            self.java_component_str().to_owned(),
+            ctx.java_platform_key(),
+        )
+    }
+
+    /// A short human readable summary of this version, for printing in tools
+    ///
+    /// This covers the id, type, release time, required Java version, number of libraries and
+    /// main class, one per line, without listing the libraries themselves.
+    pub fn summary(&self) -> Summary<'_> {
+        Summary(self)
+    }
+
+    /// Build the classpath for the given context
+    ///
+    /// This contains every library that applies, in order, followed by the client jar. Natives
+    /// are left out, as they are extracted rather than put on the classpath. If the same library
+    /// appears more than once only the last one is kept. Libraries whose path could point outside
+    /// `libraries_dir` are left out too, see [`Artifact::safe_relative_path`].
+    #[cfg(feature = "std")]
+    pub fn classpath(
+        &self,
+        ctx: &RuleContext,
+        libraries_dir: &Path,
+        client_jar: &Path,
+    ) -> Vec<PathBuf> {
+        let libraries: Vec<_> = self
+            .libraries
+            .iter()
+            .filter(|library| library.applies_to(ctx))
+            .filter_map(|library| {
+                let has_artifact = library
+                    .downloads
+                    .as_ref()
+                    .map_or(true, |d| d.artifact.is_some());
+                if !has_artifact || library.is_native() {
+                    return None;
+                }
+                let path = library::safe_relative_path(&library.artifact_path()?).ok()?;
+                Some((library.coord()?, path))
+            })
+            .collect();
+
+        // jars with different classifiers are different files, so they don't count as duplicates
+        let mut seen = BTreeSet::new();
+        let mut classpath: Vec<_> = libraries
+            .iter()
+            .rev()
+            .filter(|(coord, _)| seen.insert((&coord.group, &coord.artifact, &coord.classifier)))
+            .map(|(_, path)| libraries_dir.join(path))
+            .collect();
+        classpath.reverse();
+        classpath.push(client_jar.to_owned());
+        classpath
+    }
+
+    /// Rewrite every URL in the version, such as to point downloads at a mirror
+    ///
+    /// `f` is called with each URL, and replaces it when it returns `Some`. This covers the
+    /// downloads, the asset index, the client and server logging configs, and every library
+    /// artifact, natives artifact and maven repository URL.
+    pub fn rewrite_urls(&mut self, f: impl Fn(&str) -> Option<String>) {
+        let rewrite = |url: &mut String| {
+            if let Some(new_url) = f(url) {
+                *url = new_url;
+            }
+        };
+
+        let downloads = &mut self.downloads;
+        rewrite(&mut downloads.client.url);
+        for download in [
+            &mut downloads.client_mappings,
+            &mut downloads.server,
+            &mut downloads.server_mappings,
+            &mut downloads.windows_server,
+        ]
+        .into_iter()
+        .flatten()
+        .chain(downloads.extra.values_mut())
+        {
+            rewrite(&mut download.url);
+        }
+        rewrite(&mut self.asset_index.url);
+        if let Some(logging) = &mut self.logging {
+            for entry in Some(&mut logging.client)
+                .into_iter()
+                .chain(logging.server.as_mut())
+            {
+                rewrite(&mut entry.file.url);
+            }
+        }
+
+        for library in &mut self.libraries {
+            if let Some(url) = &mut library.url {
+                rewrite(url);
+            }
+            library.rewrite_download_url(&f);
+        }
+    }
+
+    /// Put the rules of every library and argument in a canonical order
+    ///
+    /// This makes versions that only differ in the order of their rules compare and hash the
+    /// same. Only the order of rules whose order doesn't affect the result is changed, see
+    /// [`rule::canonicalize_rules`].
+    pub fn canonicalize(&mut self) {
+        for library in &mut self.libraries {
+            if let Some(rules) = &mut library.rules {
+                rule::canonicalize_rules(rules);
+            }
+        }
+        if let Some(arguments) = &mut self.arguments {
+            for argument in arguments.game.iter_mut().chain(&mut arguments.jvm) {
+                rule::canonicalize_rules(&mut argument.rules);
+            }
+        }
+    }
+
+    /// The names of every placeholder a launcher has to substitute in the given context
+    ///
+    /// This covers the game and JVM arguments that apply, and the logging argument.
+    pub fn required_placeholders(&self, ctx: &RuleContext) -> BTreeSet<String> {
+        let mut arguments = self.game_arguments(ctx);
+        arguments.extend(self.jvm_arguments(ctx));
+        arguments.extend(
+            self.logging
+                .iter()
+                .map(|logging| logging.client.argument.clone()),
+        );
+        arguments
+            .iter()
+            .flat_map(|argument| placeholders(argument))
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// The names of every feature the rules of the arguments check, whatever value they check for
+    ///
+    /// A launcher can use this to only offer the features a version actually uses.
+    pub fn all_referenced_features(&self) -> BTreeSet<String> {
+        self.arguments
+            .iter()
+            .flat_map(|arguments| arguments.game.iter().chain(&arguments.jvm))
+            .flat_map(|argument| &argument.rules)
+            .flat_map(|rule| rule.required_features().iter())
+            .map(|(name, _)| name.to_owned())
+            .collect()
+    }
+
+    /// Every library artifact to download in the given context
+    ///
+    /// This is the main artifact of each library that applies, along with the natives it
+    /// contributes, from either natives model. See [`Library::natives_for`].
+    pub fn artifacts<'a>(&'a self, ctx: &'a RuleContext) -> impl Iterator<Item = &'a Artifact> {
+        self.library_artifacts(ctx).map(|(_, artifact)| artifact)
+    }
+
+    /// Like [`Version::artifacts`], along with the library each artifact belongs to
+    pub(crate) fn library_artifacts<'a>(
+        &'a self,
+        ctx: &'a RuleContext,
+    ) -> impl Iterator<Item = (&'a Library, &'a Artifact)> {
+        let natives_ctx = self.natives_context(ctx);
+        self.libraries
+            .iter()
+            .filter(move |library| library.applies_to(ctx))
+            .flat_map(move |library| {
+                let artifact = match &library.downloads {
+                    Some(downloads) if !library.is_native() => downloads.artifact.as_ref(),
+                    _ => None,
+                };
+                artifact
+                    .into_iter()
+                    .chain(library.natives_for(natives_ctx.as_ref().unwrap_or(ctx)))
+                    .map(move |artifact| (library, artifact))
+            })
+    }
+
+    /// The natives jars to extract in the given context, each with the library it comes from
+    ///
+    /// Only libraries whose rules allow them are included, for both natives models, see
+    /// [`Library::natives_for`]. Files to leave out when extracting are given by the
+    /// [`Library::extract`] of each library.
+    ///
+    /// On Apple Silicon, versions without arm64 natives get the x86_64 macOS natives instead, see
+    /// [`Version::requires_x64_java_on_arm_mac`].
+    pub fn natives_libraries(&self, ctx: &RuleContext) -> Vec<(&Library, &Artifact)> {
+        let natives_ctx = self.natives_context(ctx);
+        let natives_ctx = natives_ctx.as_ref().unwrap_or(ctx);
+        self.libraries
+            .iter()
+            .flat_map(|library| {
+                library
+                    .natives_for(natives_ctx)
+                    .into_iter()
+                    .map(move |artifact| (library, artifact))
+            })
+            .collect()
+    }
+
+    /// Whether this version has macOS natives, but none for arm64
+    ///
+    /// Versions from before Mojang shipped arm64 natives only have `natives-macos` or
+    /// `natives-osx`, which can only be loaded by an x86_64 JVM running under Rosetta. On Apple
+    /// Silicon the launcher has to pick such a JVM, and the natives resolve to the x86_64 ones.
+    pub fn requires_x64_java_on_arm_mac(&self) -> bool {
+        let mac = |arch: &str| {
+            RuleContext {
+                os_name: Some(OsName::Osx),
+                arch: Some(arch.to_owned()),
+                ..RuleContext::default()
+            }
+        };
+        let arm = mac("aarch64");
+        let arm_classifier = arm.native_classifier();
+        let has_arm_natives = self
+            .libraries
+            .iter()
+            .filter(|library| library.applies_to(&arm))
+            .any(|library| {
+                library.classifier(&arm_classifier).is_some()
+                    || library.coord().and_then(|coord| coord.classifier).as_ref()
+                        == Some(&arm_classifier)
+            });
+        let x64 = mac("x86_64");
+        !has_arm_natives
+            && self
+                .libraries
+                .iter()
+                .any(|library| !library.natives_for(&x64).is_empty())
+    }
+
+    /// The context to resolve natives in, if it differs from `ctx`
+    fn natives_context(&self, ctx: &RuleContext) -> Option<RuleContext> {
+        (ctx.is_arm_mac() && self.requires_x64_java_on_arm_mac()).then(|| {
+            RuleContext {
+                arch: Some("x86_64".to_owned()),
+                ..ctx.clone()
+            }
+        })
+    }
+
+    /// The number of bytes to download for a full install in the given context, leaving out the
+    /// asset objects
+    ///
+    /// This is the client jar, every library and natives jar that applies, the asset index and
+    /// the logging config. Use [`Version::total_download_size_with_assets`] to include the assets.
+    pub fn total_download_size(&self, ctx: &RuleContext) -> u64 {
+        let libraries: u64 = self.artifacts(ctx).map(|artifact| artifact.size).sum();
+        let logging = self
+            .logging
+            .as_ref()
+            .map_or(0, |logging| logging.client.file.size);
+        self.downloads.client.size + libraries + self.asset_index.size + logging
+    }
+
+    /// The number of bytes to download for a full install in the given context, including the
+    /// asset objects from the asset index
+    pub fn total_download_size_with_assets(
+        &self,
+        ctx: &RuleContext,
+        objects: &crate::asset_index::AssetIndex,
+    ) -> u64 {
+        self.total_download_size(ctx) + objects.total_size()
+    }
+
+    /// The libraries needed to run a server in the given context
+    ///
+    /// This is every library that applies, leaving out natives and the libraries only the client
+    /// uses. The vanilla server bundles its own libraries, but mod loaders read them from here.
+    pub fn server_libraries(&self, ctx: &RuleContext) -> Vec<&Library> {
+        self.libraries
+            .iter()
+            .filter(|library| library.applies_to(ctx))
+            .filter(|library| !library.is_native() && !library.is_client_only())
+            .collect()
+    }
+
+    /// Build the game arguments for the given context, without substituting placeholders
+    ///
+    /// For versions that use the `arguments` field this applies the rules of each argument. Older
+    /// versions only have the `minecraftArguments` string, which is split on whitespace.
+    pub fn game_arguments(&self, ctx: &RuleContext) -> Vec<String> {
+        match (&self.arguments, &self.minecraft_arguments) {
+            (Some(arguments), _) => arguments.build_game(ctx),
+            (None, Some(legacy)) => legacy.split_whitespace().map(str::to_owned).collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Build the JVM arguments for the given context, without substituting placeholders
+    ///
+    /// Versions without the `arguments` field don't specify any JVM arguments, so the ones the
+    /// vanilla launcher hardcodes for them are used instead. See [`Arguments::legacy_jvm`].
+    pub fn jvm_arguments(&self, ctx: &RuleContext) -> Vec<String> {
+        match &self.arguments {
+            Some(arguments) => arguments.build_jvm(ctx),
+            None => build_arguments(&Arguments::legacy_jvm(), ctx),
+        }
+    }
+}
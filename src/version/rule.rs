@@ -8,45 +8,677 @@
 //! Rules that must pass before a field is considered "valid," ie native libraries for a specific
 //! OS, or features that must be enabled.
 
-use std::collections::BTreeMap;
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
 pub enum OsName {
     Windows,
     Osx,
     Linux,
+    /// An OS this crate doesn't know about yet, holding the raw value
+    Other(String),
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+impl OsName {
+    /// The name of this OS as it appears in JSON
+    pub fn as_str(&self) -> &str {
+        match self {
+            OsName::Windows => "windows",
+            OsName::Osx => "osx",
+            OsName::Linux => "linux",
+            OsName::Other(other) => other,
+        }
+    }
+
+    /// The OS this crate was compiled for, if it is one Minecraft supports
+    pub fn current() -> Option<Self> {
+        if cfg!(target_os = "windows") {
+            Some(OsName::Windows)
+        } else if cfg!(target_os = "macos") {
+            Some(OsName::Osx)
+        } else if cfg!(target_os = "linux") {
+            Some(OsName::Linux)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<&str> for OsName {
+    fn from(s: &str) -> Self {
+        match s {
+            "windows" => OsName::Windows,
+            "osx" => OsName::Osx,
+            "linux" => OsName::Linux,
+            other => OsName::Other(other.to_owned()),
+        }
+    }
+}
+
+impl Serialize for OsName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Unknown names deserialize into [`OsName::Other`] so new platforms don't break parsing
+impl<'de> Deserialize<'de> for OsName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(OsName::from(s.as_str()))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
 pub enum OsArch {
     X86,
+    /// An architecture this crate doesn't know about yet, holding the raw value
+    Other(String),
+}
+
+impl OsArch {
+    /// The name of this architecture as it appears in JSON
+    pub fn as_str(&self) -> &str {
+        match self {
+            OsArch::X86 => "x86",
+            OsArch::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for OsArch {
+    fn from(s: &str) -> Self {
+        match s {
+            "x86" => OsArch::X86,
+            other => OsArch::Other(other.to_owned()),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+impl Serialize for OsArch {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Unknown architectures deserialize into [`OsArch::Other`], like [`OsName`]
+impl<'de> Deserialize<'de> for OsArch {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(OsArch::from(s.as_str()))
+    }
+}
+
+/// The OS condition of a rule, see [`Rule::os_condition`]
+///
+/// Every field that is present must match. The accessors give the values as they appear in JSON.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Os {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub name: Option<OsName>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub arch: Option<OsArch>,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// What a rule does when it matches
+///
+/// Anything other than `allow` or `disallow` is rejected when parsing. Unlike the other enums
+/// there is no `Other` fallback, as there is no safe way to evaluate a rule with an unknown
+/// action.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
+#[non_exhaustive]
 pub enum RuleAction {
     Allow,
     Disallow,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+/// A set of feature flags
+///
+/// In a rule these are the values each feature must have for the rule to match. In a
+/// [`RuleContext`] these are the features that are enabled. The features known to be used by the
+/// vanilla manifests have named fields, anything else ends up in `other`.
+///
+/// Values written as `1`/`0` or `"true"`/`"false"` are accepted, as some tools write them that
+/// way, but they are always serialized as booleans.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct Features {
+    #[serde(
+        default,
+        deserialize_with = "crate::de::option_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_demo_user: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "crate::de::option_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub has_custom_resolution: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "crate::de::option_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub has_quick_plays_support: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "crate::de::option_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_quick_play_singleplayer: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "crate::de::option_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_quick_play_multiplayer: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "crate::de::option_lenient_bool",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub is_quick_play_realms: Option<bool>,
+    /// Features this crate doesn't know about
+    #[serde(flatten, deserialize_with = "crate::de::map_of_lenient_bool")]
+    pub other: BTreeMap<String, bool>,
+}
+
+impl Features {
+    fn known(&self) -> [(&'static str, Option<bool>); 6] {
+        [
+            ("is_demo_user", self.is_demo_user),
+            ("has_custom_resolution", self.has_custom_resolution),
+            ("has_quick_plays_support", self.has_quick_plays_support),
+            (
+                "is_quick_play_singleplayer",
+                self.is_quick_play_singleplayer,
+            ),
+            ("is_quick_play_multiplayer", self.is_quick_play_multiplayer),
+            ("is_quick_play_realms", self.is_quick_play_realms),
+        ]
+    }
+
+    fn known_mut(&mut self, name: &str) -> Option<&mut Option<bool>> {
+        match name {
+            "is_demo_user" => Some(&mut self.is_demo_user),
+            "has_custom_resolution" => Some(&mut self.has_custom_resolution),
+            "has_quick_plays_support" => Some(&mut self.has_quick_plays_support),
+            "is_quick_play_singleplayer" => Some(&mut self.is_quick_play_singleplayer),
+            "is_quick_play_multiplayer" => Some(&mut self.is_quick_play_multiplayer),
+            "is_quick_play_realms" => Some(&mut self.is_quick_play_realms),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a feature, or `None` if it isn't present
+    pub fn get(&self, name: &str) -> Option<bool> {
+        match self.known().iter().find(|(known, _)| *known == name) {
+            Some((_, value)) => *value,
+            None => self.other.get(name).copied(),
+        }
+    }
+
+    /// Whether a feature is present and set to `true`
+    pub fn is_set(&self, name: &str) -> bool {
+        self.get(name).unwrap_or(false)
+    }
+
+    /// Set the value of a feature
+    pub fn set(&mut self, name: impl Into<String>, value: bool) {
+        let name = name.into();
+        match self.known_mut(&name) {
+            Some(field) => *field = Some(value),
+            None => {
+                self.other.insert(name, value);
+            }
+        }
+    }
+
+    /// Iterate over every present feature and its value
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> {
+        self.known()
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|value| (name, value)))
+            .chain(
+                self.other
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), *value)),
+            )
+    }
+
+    /// Whether no features are present
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Rule {
     pub action: RuleAction,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub os: Option<Os>,
-    #[serde(default)]
-    pub features: BTreeMap<String, bool>,
+    #[serde(default, skip_serializing_if = "Features::is_empty")]
+    pub features: Features,
+}
+
+impl Os {
+    /// The OS the rule applies to, if it checks it
+    pub fn name(&self) -> Option<&OsName> {
+        self.name.as_ref()
+    }
+
+    /// The architecture the rule applies to as it appears in JSON, ie `x86`, if it checks it
+    pub fn arch(&self) -> Option<&str> {
+        self.arch.as_ref().map(OsArch::as_str)
+    }
+
+    /// The pattern the OS version must match, ie `^10\\.`, if the rule checks it
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Check if this OS condition holds for the given context
+    ///
+    /// Every present field must match. Fields the context does not know about never match.
+    pub fn matches(&self, ctx: &RuleContext) -> bool {
+        let name_matches = match &self.name {
+            Some(name) => ctx.os_name.as_ref() == Some(name),
+            None => true,
+        };
+        let arch_matches = match &self.arch {
+            Some(arch) => ctx.arch.as_deref() == Some(arch.as_str()),
+            None => true,
+        };
+        let version_matches = match (&self.version, &ctx.os_version) {
+            (Some(pattern), Some(version)) => version_pattern_matches(pattern, version),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        name_matches && arch_matches && version_matches
+    }
+}
+
+/// One character of an OS version pattern, see [`version_pattern_matches`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum PatternChar {
+    Literal(char),
+    /// `\d`
+    Digit,
+    /// An unescaped `.`
+    Any,
+}
+
+impl PatternChar {
+    fn matches(self, c: char) -> bool {
+        match self {
+            PatternChar::Literal(literal) => c == literal,
+            PatternChar::Digit => c.is_ascii_digit(),
+            PatternChar::Any => true,
+        }
+    }
+}
+
+/// Match an OS version against the pattern used in rules
+///
+/// Mojang specifies these as regular expressions, written as `"^10\\."` or, in older manifests,
+/// `"^10\\.5\\.\\d$"` in the JSON. Rather than pulling in a regex engine, this supports the
+/// subset they use: `^` and `$` anchors, `.`, `\d`, and escaped characters. Patterns using
+/// anything else, such as repetition or groups, never match.
+fn version_pattern_matches(pattern: &str, version: &str) -> bool {
+    let (start_anchored, pattern) = match pattern.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let (end_anchored, pattern) = match pattern.strip_suffix('$') {
+        // an escaped `$` at the end is a literal
+        Some(rest) if !rest.ends_with('\\') || rest.ends_with("\\\\") => (true, rest),
+        _ => (false, pattern),
+    };
+
+    let mut tokens = Vec::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '\\' => {
+                match chars.next() {
+                    Some('d') => PatternChar::Digit,
+                    Some(escaped) if !escaped.is_ascii_alphanumeric() => {
+                        PatternChar::Literal(escaped)
+                    }
+                    _ => return false,
+                }
+            }
+            '.' => PatternChar::Any,
+            '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' => return false,
+            c => PatternChar::Literal(c),
+        };
+        tokens.push(token);
+    }
+
+    let version: Vec<char> = version.chars().collect();
+    let matches_at = |start: usize| {
+        let rest = &version[start..];
+        rest.len() >= tokens.len()
+            && (!end_anchored || rest.len() == tokens.len())
+            && tokens.iter().zip(rest).all(|(token, &c)| token.matches(c))
+    };
+    if start_anchored {
+        matches_at(0)
+    } else {
+        (0..=version.len()).any(matches_at)
+    }
+}
+
+impl Rule {
+    /// The values features must have for this rule to match, empty if it doesn't check any
+    pub fn required_features(&self) -> &Features {
+        &self.features
+    }
+
+    /// The OS this rule checks, if any, ie to show which platforms an argument applies to
+    pub fn os_condition(&self) -> Option<&Os> {
+        self.os.as_ref()
+    }
+
+    /// Check if every condition of this rule holds for the given context
+    ///
+    /// A rule with both an `os` and `features` only matches when the OS matches and every
+    /// feature has the given value. This does not take the action into account; see
+    /// [`is_allowed`] for that.
+    pub fn matches(&self, ctx: &RuleContext) -> bool {
+        let os_matches = self.os.as_ref().map_or(true, |os| os.matches(ctx));
+        os_matches
+            && self
+                .features
+                .iter()
+                .all(|(name, value)| ctx.feature(name) == value)
+    }
+}
+
+/// Evaluate a list of rules against a context
+///
+/// An empty list always allows. Otherwise the default is to disallow, and every matching rule
+/// overrides the result with its action, so the last matching rule wins.
+pub fn is_allowed(rules: &[Rule], ctx: &RuleContext) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+    rules
+        .iter()
+        .filter(|rule| rule.matches(ctx))
+        .fold(false, |_, rule| rule.action == RuleAction::Allow)
+}
+
+/// Put a list of rules in a canonical order, so equivalent lists compare and hash the same
+///
+/// Since the last matching rule wins, the order of rules with different actions matters and is
+/// kept. Only runs of consecutive rules with the same action are sorted, by OS and then features,
+/// with duplicates removed, as their order can't change the result.
+pub fn canonicalize_rules(rules: &mut Vec<Rule>) {
+    let mut runs: Vec<Vec<Rule>> = Vec::new();
+    for rule in rules.drain(..) {
+        match runs.last_mut() {
+            Some(run) if run[0].action == rule.action => run.push(rule),
+            _ => runs.push(vec![rule]),
+        }
+    }
+    for mut run in runs {
+        run.sort();
+        run.dedup();
+        rules.append(&mut run);
+    }
+}
+
+/// The environment that rules are evaluated against
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RuleContext {
+    /// The OS the game will run on
+    pub os_name: Option<OsName>,
+    /// The version of the OS, ie `10.0` for Windows 10
+    pub os_version: Option<String>,
+    /// The CPU architecture, using Rust's naming from `std::env::consts::ARCH` (`x86`, `x86_64`,
+    /// `aarch64`, ...)
+    pub arch: Option<String>,
+    /// Enabled features, such as `is_demo_user` or `has_custom_resolution`
+    pub features: Features,
+}
+
+impl RuleContext {
+    /// A context for the machine this is running on, with no features enabled
+    ///
+    /// The OS name and architecture come from the compilation target. The OS version can't be
+    /// detected without platform specific code, so it is left unset and rules that check it will
+    /// not match. Set [`RuleContext::os_version`] yourself if you need those rules to apply.
+    #[cfg(feature = "std")]
+    pub fn current() -> Self {
+        RuleContext {
+            os_name: OsName::current(),
+            os_version: None,
+            arch: Some(std::env::consts::ARCH.to_owned()),
+            features: Features::default(),
+        }
+    }
+
+    /// Set a feature, for chaining off a constructor
+    pub fn with_feature(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.features.set(name, enabled);
+        self
+    }
+
+    /// A preset with `is_demo_user` enabled, to launch the demo
+    ///
+    /// Like the other presets this starts from the default context, without an OS or
+    /// architecture, and returns a builder so more features and placeholders can be added. Use
+    /// [`RuleContextBuilder::demo_user`] to start from another context instead.
+    pub fn demo() -> RuleContextBuilder {
+        RuleContextBuilder::default().demo_user()
+    }
+
+    /// A preset with `has_custom_resolution` enabled and the window size registered, see
+    /// [`RuleContext::demo`]
+    pub fn with_resolution(width: u32, height: u32) -> RuleContextBuilder {
+        RuleContextBuilder::default().custom_resolution(width, height)
+    }
+
+    /// A preset with `is_quick_play_singleplayer` enabled and the world to join registered, see
+    /// [`RuleContext::demo`]
+    pub fn quick_play_singleplayer(world: impl Into<String>) -> RuleContextBuilder {
+        RuleContextBuilder::default().quick_play_singleplayer(world)
+    }
+
+    /// Whether the architecture is 32 or 64 bit, as used for `${arch}` in natives classifiers
+    ///
+    /// Defaults to 64 when the architecture is unknown.
+    pub fn arch_bits(&self) -> u8 {
+        match self.arch.as_deref() {
+            Some("x86" | "arm") => 32,
+            _ => 64,
+        }
+    }
+
+    /// The classifier Mojang uses for natives jars built for this context, such as
+    /// `natives-windows-arm64`
+    ///
+    /// An unknown architecture is treated as `x86_64`, and an unknown or missing OS as Linux.
+    pub fn native_classifier(&self) -> String {
+        let arch = self.arch.as_deref();
+        let classifier = match self.os_name {
+            Some(OsName::Windows) => {
+                match arch {
+                    Some("aarch64") => "natives-windows-arm64",
+                    Some("x86") => "natives-windows-x86",
+                    _ => "natives-windows",
+                }
+            }
+            Some(OsName::Osx) => {
+                match arch {
+                    Some("aarch64") => "natives-macos-arm64",
+                    _ => "natives-macos",
+                }
+            }
+            Some(OsName::Linux | OsName::Other(_)) | None => {
+                match arch {
+                    Some("aarch64") => "natives-linux-arm64",
+                    Some("arm") => "natives-linux-arm32",
+                    _ => "natives-linux",
+                }
+            }
+        };
+        classifier.to_owned()
+    }
+
+    /// The platform Mojang's Java runtime index lists runtimes for this context under, such as
+    /// `windows-x64` or `mac-os-arm64`
+    ///
+    /// Like [`RuleContext::native_classifier`], an unknown architecture is treated as `x86_64`,
+    /// and an unknown or missing OS as Linux. Mojang has no Linux runtimes for ARM, so those get
+    /// `linux` too.
+    pub fn java_platform_key(&self) -> &'static str {
+        let arch = self.arch.as_deref();
+        match self.os_name {
+            Some(OsName::Windows) => {
+                match arch {
+                    Some("aarch64") => "windows-arm64",
+                    Some("x86") => "windows-x86",
+                    _ => "windows-x64",
+                }
+            }
+            Some(OsName::Osx) => {
+                match arch {
+                    Some("aarch64") => "mac-os-arm64",
+                    _ => "mac-os",
+                }
+            }
+            Some(OsName::Linux | OsName::Other(_)) | None => {
+                match arch {
+                    Some("x86") => "linux-i386",
+                    _ => "linux",
+                }
+            }
+        }
+    }
+
+    /// Whether this is macOS on Apple Silicon, where versions without arm64 natives need an
+    /// x86_64 JVM running under Rosetta
+    pub fn is_arm_mac(&self) -> bool {
+        self.os_name == Some(OsName::Osx) && self.arch.as_deref() == Some("aarch64")
+    }
+
+    /// Get the value of a feature, defaulting to `false` if it is not set
+    pub fn feature(&self, name: &str) -> bool {
+        self.features.is_set(name)
+    }
+}
+
+/// Builds a [`RuleContext`] together with the placeholder values its features need
+///
+/// Features like `has_custom_resolution` enable arguments containing placeholders such as
+/// `${resolution_width}`. Enabling a feature through this builder registers the values of those
+/// placeholders at the same time, so a feature can't be enabled without them.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RuleContextBuilder {
+    ctx: RuleContext,
+    vars: BTreeMap<String, String>,
+}
+
+impl RuleContextBuilder {
+    /// Start from an existing context, ie [`RuleContext::current`]
+    pub fn new(ctx: RuleContext) -> Self {
+        RuleContextBuilder {
+            ctx,
+            vars: BTreeMap::new(),
+        }
+    }
+
+    fn enable(mut self, feature: &str, vars: &[(&str, String)]) -> Self {
+        self.ctx.features.set(feature, true);
+        for (name, value) in vars {
+            self.vars.insert((*name).to_owned(), value.clone());
+        }
+        self
+    }
+
+    /// Enable `is_demo_user`, which needs no placeholders
+    pub fn demo_user(self) -> Self {
+        self.enable("is_demo_user", &[])
+    }
+
+    /// Enable `has_custom_resolution` with `${resolution_width}` and `${resolution_height}`
+    pub fn custom_resolution(self, width: u32, height: u32) -> Self {
+        self.enable(
+            "has_custom_resolution",
+            &[
+                ("resolution_width", width.to_string()),
+                ("resolution_height", height.to_string()),
+            ],
+        )
+    }
+
+    /// Enable `has_quick_plays_support` with `${quickPlayPath}`, the file quick play logs to
+    pub fn quick_play_path(self, path: impl Into<String>) -> Self {
+        self.enable("has_quick_plays_support", &[("quickPlayPath", path.into())])
+    }
+
+    /// Enable `is_quick_play_singleplayer` with `${quickPlaySingleplayer}`, the world to join
+    pub fn quick_play_singleplayer(self, world: impl Into<String>) -> Self {
+        self.enable(
+            "is_quick_play_singleplayer",
+            &[("quickPlaySingleplayer", world.into())],
+        )
+    }
+
+    /// Enable `is_quick_play_multiplayer` with `${quickPlayMultiplayer}`, the server to join
+    pub fn quick_play_multiplayer(self, server: impl Into<String>) -> Self {
+        self.enable(
+            "is_quick_play_multiplayer",
+            &[("quickPlayMultiplayer", server.into())],
+        )
+    }
+
+    /// Enable `is_quick_play_realms` with `${quickPlayRealms}`, the realm to join
+    pub fn quick_play_realms(self, realm: impl Into<String>) -> Self {
+        self.enable("is_quick_play_realms", &[("quickPlayRealms", realm.into())])
+    }
+
+    /// Set the value of any other placeholder
+    pub fn var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// The context for matching rules, and the placeholder values keyed by placeholder name
+    pub fn build(self) -> (RuleContext, BTreeMap<String, String>) {
+        (self.ctx, self.vars)
+    }
+}
+
+impl From<RuleContext> for RuleContextBuilder {
+    fn from(ctx: RuleContext) -> Self {
+        RuleContextBuilder::new(ctx)
+    }
 }
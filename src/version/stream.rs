@@ -0,0 +1,171 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Streaming access to the libraries of a version JSON file, without parsing the whole file.
+//!
+//! This is only available with the `std` feature.
+
+use std::io::{BufRead, BufReader, Read};
+
+use serde::de::Error as _;
+use serde::Deserialize;
+
+use crate::version::library::Library;
+
+/// Deserialize the libraries of a version JSON file one at a time
+///
+/// Everything before the `libraries` array is skipped over without being deserialized, and
+/// reading stops once the array ends. Only one library is held in memory at a time.
+///
+/// If the file has no `libraries` field the iterator is empty. After an error is returned the
+/// iterator ends.
+pub fn stream_libraries<R: Read>(reader: R) -> LibraryStream<R> {
+    LibraryStream {
+        reader: BufReader::new(reader),
+        state: State::Searching,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum State {
+    Searching,
+    First,
+    Rest,
+    Done,
+}
+
+/// Iterator over the libraries of a version JSON file, see [`stream_libraries`]
+#[derive(Debug)]
+pub struct LibraryStream<R> {
+    reader: BufReader<R>,
+    state: State,
+}
+
+impl<R: Read> LibraryStream<R> {
+    fn peek(&mut self) -> serde_json::Result<Option<u8>> {
+        let buf = self.reader.fill_buf().map_err(serde_json::Error::io)?;
+        Ok(buf.first().copied())
+    }
+
+    fn next_byte(&mut self) -> serde_json::Result<u8> {
+        let byte = self
+            .peek()?
+            .ok_or_else(|| serde_json::Error::custom("unexpected end of input"))?;
+        self.reader.consume(1);
+        Ok(byte)
+    }
+
+    fn skip_whitespace(&mut self) -> serde_json::Result<()> {
+        while let Some(b' ' | b'\n' | b'\r' | b'\t') = self.peek()? {
+            self.reader.consume(1);
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: u8) -> serde_json::Result<()> {
+        self.skip_whitespace()?;
+        let byte = self.next_byte()?;
+        if byte == expected {
+            Ok(())
+        } else {
+            Err(serde_json::Error::custom(format_args!(
+                "expected `{}`, found `{}`",
+                expected as char, byte as char
+            )))
+        }
+    }
+
+    /// Read the rest of a string after the opening quote, returning its raw contents
+    fn read_string(&mut self) -> serde_json::Result<Vec<u8>> {
+        let mut contents = Vec::new();
+        loop {
+            match self.next_byte()? {
+                b'"' => return Ok(contents),
+                b'\\' => {
+                    contents.push(b'\\');
+                    contents.push(self.next_byte()?);
+                }
+                byte => contents.push(byte),
+            }
+        }
+    }
+
+    /// Skip ahead to just after the opening bracket of the top level `libraries` array
+    ///
+    /// Returns `false` if the top level object ends without one.
+    fn find_libraries(&mut self) -> serde_json::Result<bool> {
+        self.expect(b'{')?;
+        let mut depth = 1;
+        loop {
+            match self.next_byte()? {
+                b'"' => {
+                    let contents = self.read_string()?;
+                    if depth != 1 {
+                        continue;
+                    }
+                    self.skip_whitespace()?;
+                    if self.peek()? == Some(b':') {
+                        self.reader.consume(1);
+                        if contents == b"libraries" {
+                            self.expect(b'[')?;
+                            return Ok(true);
+                        }
+                    }
+                }
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(false);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn next_library(&mut self) -> serde_json::Result<Option<Library>> {
+        if self.state == State::Searching {
+            self.state = if self.find_libraries()? {
+                State::First
+            } else {
+                State::Done
+            };
+        }
+        if self.state == State::Done {
+            return Ok(None);
+        }
+
+        self.skip_whitespace()?;
+        if self.peek()? == Some(b']') {
+            self.reader.consume(1);
+            self.state = State::Done;
+            return Ok(None);
+        }
+        if self.state == State::Rest {
+            self.expect(b',')?;
+        }
+        self.state = State::Rest;
+
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut self.reader);
+        Library::deserialize(&mut deserializer).map(Some)
+    }
+}
+
+impl<R: Read> Iterator for LibraryStream<R> {
+    type Item = serde_json::Result<Library>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_library() {
+            Ok(library) => library.map(Ok),
+            Err(err) => {
+                self.state = State::Done;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+////////////////////////////////////////////////////////////////////////////////
+// Copyright (c) 2023. Rob Bailey                                              /
+// This Source Code Form is subject to the terms of the Mozilla Public         /
+// License, v. 2.0. If a copy of the MPL was not distributed with this         /
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
+////////////////////////////////////////////////////////////////////////////////
+
+//! Sanity checks for a [`Version`], to catch structural problems before launching.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::version::library::Library;
+use crate::version::maven::MavenCoord;
+use crate::version::{placeholders, Version};
+
+/// The placeholders a launcher is expected to substitute in arguments
+///
+/// This is the set used by vanilla versions, plus `classpath_separator` and `library_directory`
+/// which are commonly used by mod loaders.
+pub const KNOWN_PLACEHOLDERS: &[&str] = &[
+    "assets_index_name",
+    "assets_root",
+    "auth_access_token",
+    "auth_player_name",
+    "auth_session",
+    "auth_uuid",
+    "auth_xuid",
+    "classpath",
+    "classpath_separator",
+    "clientid",
+    "game_assets",
+    "game_directory",
+    "launcher_name",
+    "launcher_version",
+    "library_directory",
+    "natives_directory",
+    "quickPlayMultiplayer",
+    "quickPlayPath",
+    "quickPlayRealms",
+    "quickPlaySingleplayer",
+    "resolution_height",
+    "resolution_width",
+    "user_properties",
+    "user_type",
+    "version_name",
+    "version_type",
+];
+
+/// A problem found by [`Version::validate`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ValidationError {
+    /// `mainClass` is empty
+    EmptyMainClass,
+    /// The client download has no URL
+    MissingClientDownload,
+    /// A library name isn't a valid maven coordinate, holding the name
+    InvalidLibraryName(String),
+    /// A library has neither an artifact nor natives to download, and no URL can be derived for
+    /// it, holding its name
+    LibraryWithoutDownloads(String),
+    /// An argument contains a placeholder that isn't in [`KNOWN_PLACEHOLDERS`]
+    UnknownPlaceholder {
+        argument: String,
+        placeholder: String,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::EmptyMainClass => f.write_str("main class is empty"),
+            ValidationError::MissingClientDownload => f.write_str("client download has no url"),
+            ValidationError::InvalidLibraryName(name) => {
+                write!(f, "library name `{}` is not a maven coordinate", name)
+            }
+            ValidationError::LibraryWithoutDownloads(name) => {
+                write!(f, "library `{}` has no artifact or natives", name)
+            }
+            ValidationError::UnknownPlaceholder {
+                argument,
+                placeholder,
+            } => {
+                write!(
+                    f,
+                    "argument `{}` contains unknown placeholder `${{{}}}`",
+                    argument, placeholder
+                )
+            }
+        }
+    }
+}
+
+impl crate::error::StdError for ValidationError {}
+
+fn validate_library(library: &Library, errors: &mut Vec<ValidationError>) {
+    if MavenCoord::parse(&library.name).is_none() {
+        errors.push(ValidationError::InvalidLibraryName(library.name.clone()));
+    }
+    if library.resolved_url().is_none() && library.natives.is_none() {
+        errors.push(ValidationError::LibraryWithoutDownloads(
+            library.name.clone(),
+        ));
+    }
+}
+
+fn validate_argument(argument: &str, errors: &mut Vec<ValidationError>) {
+    for placeholder in placeholders(argument) {
+        if !KNOWN_PLACEHOLDERS.contains(&placeholder) {
+            errors.push(ValidationError::UnknownPlaceholder {
+                argument: argument.to_owned(),
+                placeholder: placeholder.to_owned(),
+            });
+        }
+    }
+}
+
+impl Version {
+    /// Check the version for structural problems that would stop it from launching
+    ///
+    /// Every problem found is returned, rather than stopping at the first one.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.main_class.trim().is_empty() {
+            errors.push(ValidationError::EmptyMainClass);
+        }
+        if self.downloads.client.url.is_empty() {
+            errors.push(ValidationError::MissingClientDownload);
+        }
+        for library in &self.libraries {
+            validate_library(library, &mut errors);
+        }
+
+        if let Some(arguments) = &self.arguments {
+            for argument in arguments.game.iter().chain(&arguments.jvm) {
+                for value in &argument.values {
+                    validate_argument(value, &mut errors);
+                }
+            }
+        }
+        if let Some(arguments) = &self.minecraft_arguments {
+            for argument in arguments.split_whitespace() {
+                validate_argument(argument, &mut errors);
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -5,9 +5,17 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.                   /
 ////////////////////////////////////////////////////////////////////////////////
 
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "chrono")]
+use core::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::io::Read;
+
 use serde::{Deserialize, Serialize};
 
-use crate::VersionKind;
+use crate::sha1::Sha1;
+use crate::{Error, VersionKind};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -19,6 +27,7 @@ pub struct Latest {
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Version {
     pub id: String,
     pub url: String,
@@ -26,16 +35,132 @@ pub struct Version {
     pub release_time: String,
     #[serde(rename = "type")]
     pub kind: VersionKind,
+    /// The hash of the version JSON file, only present in the v2 manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<Sha1>,
+    /// Only present in the v2 manifest, see `Version.compliance_level` in the version JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compliance_level: Option<u8>,
+}
+
+impl Version {
+    /// Create an entry as it appears in the v1 manifest, without a hash or compliance level
+    pub fn new(
+        id: impl Into<String>,
+        kind: VersionKind,
+        url: impl Into<String>,
+        time: impl Into<String>,
+        release_time: impl Into<String>,
+    ) -> Self {
+        Version {
+            id: id.into(),
+            url: url.into(),
+            time: time.into(),
+            release_time: release_time.into(),
+            kind,
+            sha1: None,
+            compliance_level: None,
+        }
+    }
+
+    /// Every field, for breaking ties when ordering
+    #[cfg(feature = "chrono")]
+    #[allow(clippy::type_complexity)]
+    fn sort_key(&self) -> (&str, &str, &str, &str, &str, Option<Sha1>, Option<u8>) {
+        (
+            &self.id,
+            &self.url,
+            &self.time,
+            &self.release_time,
+            self.kind.as_str(),
+            self.sha1,
+            self.compliance_level,
+        )
+    }
+
+    /// Parse `release_time`, the time the version was first released
+    #[cfg(feature = "chrono")]
+    pub fn released_at(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::parse_time(&self.release_time)
+    }
+
+    /// Parse `time`, the time the version was last updated
+    #[cfg(feature = "chrono")]
+    pub fn updated_at(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+        crate::parse_time(&self.time)
+    }
+}
+
+/// Compare release times, newest first, falling back to comparing the strings if either can't be
+/// parsed
+#[cfg(feature = "chrono")]
+fn cmp_newest_first(a: &Version, b: &Version) -> Ordering {
+    match (a.released_at(), b.released_at()) {
+        (Ok(a), Ok(b)) => b.cmp(&a),
+        _ => b.release_time.cmp(&a.release_time),
+    }
+}
+
+/// Orders entries by release time, newest first
+///
+/// Entries released at the same time are ordered by their other fields, so that this agrees
+/// with `==`. Use [`Manifest::sorted_by_release`] to keep manifest order for those instead.
+#[cfg(feature = "chrono")]
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_newest_first(self, other).then_with(|| self.sort_key().cmp(&other.sort_key()))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
+#[non_exhaustive]
 pub struct Manifest {
     pub latest: Latest,
     pub versions: Vec<Version>,
 }
 
 impl Manifest {
+    pub fn new(latest: Latest, versions: Vec<Version>) -> Self {
+        Manifest { latest, versions }
+    }
+
+    /// Read a manifest from JSON
+    ///
+    /// A leading byte order mark is skipped, see [`crate::version::Version::from_reader`].
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        let reader = crate::de::skip_bom(reader).map_err(serde_json::Error::io)?;
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Parse a manifest from JSON bytes
+    pub fn from_slice(json: &[u8]) -> Result<Self, Error> {
+        Ok(serde_json::from_slice(crate::de::strip_bom(json))?)
+    }
+
+    /// Parse a manifest from a JSON string
+    pub fn from_str_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(crate::de::strip_bom_str(json))?)
+    }
+
+    /// The versions sorted by release time, newest first
+    ///
+    /// The sort is stable, so versions released at the same time stay in manifest order.
+    #[cfg(feature = "chrono")]
+    pub fn sorted_by_release(&self) -> Vec<&Version> {
+        let mut versions: Vec<_> = self.versions.iter().collect();
+        versions.sort_by(|a, b| cmp_newest_first(a, b));
+        versions
+    }
+
     pub fn get_version(&self, id: &str) -> Option<&Version> {
         self.versions.iter().find(|v| v.id == id)
     }
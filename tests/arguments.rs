@@ -0,0 +1,304 @@
+mod common;
+
+use mc_launchermeta::version::rule::{OsName, Rule, RuleContext};
+use mc_launchermeta::version::{ArgWarning, Argument, ArgumentParseError, Arguments, Version};
+
+#[test]
+fn argument_from_str() {
+    let argument: Argument = "--demo".parse().unwrap();
+    assert!(argument.rules.is_empty());
+    assert_eq!(argument.values, ["--demo"]);
+
+    assert_eq!("".parse::<Argument>(), Err(ArgumentParseError::Empty));
+    assert_eq!("  ".parse::<Argument>(), Err(ArgumentParseError::Empty));
+}
+
+#[test]
+fn arguments_round_trip() {
+    let version = common::sample_version();
+    let arguments = version.arguments.unwrap();
+    let json = serde_json::to_string(&arguments).unwrap();
+    assert_eq!(serde_json::from_str::<Arguments>(&json).unwrap(), arguments);
+
+    assert_eq!(
+        serde_json::to_value(&arguments.game[22]).unwrap(),
+        serde_json::json!({
+            "rules": [{"action": "allow", "features": {"is_demo_user": true}}],
+            "value": "--demo"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(&arguments.game[0]).unwrap(),
+        serde_json::json!("--username")
+    );
+}
+
+#[test]
+fn argument_value_shape() {
+    let arguments = common::sample_version().arguments.unwrap();
+
+    let demo = serde_json::to_value(&arguments.game[22]).unwrap();
+    assert_eq!(demo["value"], serde_json::json!("--demo"));
+
+    let resolution = serde_json::to_value(&arguments.game[23]).unwrap();
+    assert_eq!(
+        resolution["value"],
+        serde_json::json!([
+            "--width",
+            "${resolution_width}",
+            "--height",
+            "${resolution_height}"
+        ])
+    );
+}
+
+#[test]
+fn argument_without_rules() {
+    let argument: Argument = serde_json::from_str(r#"{"value": "--foo"}"#).unwrap();
+    assert_eq!(argument, "--foo".parse().unwrap());
+
+    let argument: Argument = serde_json::from_str(r#"{"value": ["--width", "854"]}"#).unwrap();
+    assert!(argument.rules.is_empty());
+    assert_eq!(argument.values, ["--width", "854"]);
+
+    assert!(serde_json::from_str::<Argument>(r#"{"rules": []}"#).is_err());
+}
+
+#[test]
+fn partial_arguments() {
+    let arguments: Arguments =
+        serde_json::from_str(r#"{"jvm": ["-Dfml.ignoreInvalidMinecraftCertificates=true"]}"#)
+            .unwrap();
+    assert!(arguments.game.is_empty());
+    assert_eq!(arguments.jvm.len(), 1);
+    assert_eq!(
+        serde_json::to_value(&arguments).unwrap(),
+        serde_json::json!({
+            "game": [],
+            "jvm": ["-Dfml.ignoreInvalidMinecraftCertificates=true"]
+        })
+    );
+
+    let arguments: Arguments = serde_json::from_str(r#"{"game": ["--demo"]}"#).unwrap();
+    assert!(arguments.jvm.is_empty());
+}
+
+#[test]
+fn duplicate_flag_warnings() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+    let mut arguments = common::sample_version().arguments.unwrap();
+    let (tokens, warnings) = arguments.build_jvm_checked(&ctx);
+    assert_eq!(tokens, arguments.build_jvm(&ctx));
+    assert!(warnings.is_empty());
+    assert!(arguments.build_game_checked(&ctx).1.is_empty());
+
+    // as if merged with a parent that also sets the classpath
+    for token in [
+        "--add-opens",
+        "java.base/java.lang=ALL-UNNAMED",
+        "-cp",
+        "${classpath}",
+    ] {
+        arguments.jvm.push(token.parse().unwrap());
+    }
+    arguments
+        .jvm
+        .insert(0, "--add-opens".parse::<Argument>().unwrap());
+    arguments.jvm.insert(
+        1,
+        "java.base/java.util=ALL-UNNAMED"
+            .parse::<Argument>()
+            .unwrap(),
+    );
+    let (tokens, warnings) = arguments.build_jvm_checked(&ctx);
+    let first = tokens.iter().position(|token| token == "-cp").unwrap();
+    let second = tokens.iter().rposition(|token| token == "-cp").unwrap();
+    assert_ne!(first, second);
+    assert_eq!(
+        warnings,
+        [ArgWarning::DuplicateFlag {
+            flag: "-cp".to_owned(),
+            first,
+            second,
+        }]
+    );
+}
+
+#[test]
+fn nested_array_argument() {
+    let arguments: Arguments = serde_json::from_value(serde_json::json!({
+        "game": ["--username", "${auth_player_name}", ["--foo", "bar"]],
+        "jvm": [],
+    }))
+    .unwrap();
+    let nested = &arguments.game[2];
+    assert!(nested.rules.is_empty());
+    assert_eq!(nested.values, ["--foo", "bar"]);
+    assert_eq!(
+        arguments.build_game(&RuleContext::default()),
+        ["--username", "${auth_player_name}", "--foo", "bar"]
+    );
+
+    let json = serde_json::to_string(&arguments).unwrap();
+    assert_eq!(serde_json::from_str::<Arguments>(&json).unwrap(), arguments);
+
+    assert!(serde_json::from_str::<Argument>("[1]").is_err());
+}
+
+#[test]
+fn arguments_only() {
+    let arguments = Version::arguments_only(common::SAMPLE_VERSION).unwrap();
+    assert_eq!(arguments, common::sample_version().arguments);
+
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..RuleContext::default()
+    };
+    let legacy = common::legacy_version();
+    let arguments = Version::arguments_only(common::LEGACY_VERSION)
+        .unwrap()
+        .unwrap();
+    assert_eq!(arguments.build_game(&ctx), legacy.game_arguments(&ctx));
+    assert_eq!(arguments.build_jvm(&ctx), legacy.jvm_arguments(&ctx));
+
+    assert_eq!(Version::arguments_only(r#"{"id": "test"}"#).unwrap(), None);
+    assert!(Version::arguments_only(r#"{"arguments": {"game": 1}}"#).is_err());
+}
+
+#[test]
+fn to_legacy_string() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..RuleContext::default()
+    }
+    .with_feature("is_demo_user", true);
+    let arguments = common::sample_version().arguments.unwrap();
+
+    let legacy = arguments.to_legacy_string(&ctx);
+    assert!(legacy.starts_with(
+        "--username ${auth_player_name} --version ${version_name} --gameDir ${game_directory}"
+    ));
+    assert!(legacy.ends_with("--versionType ${version_type}"));
+    assert!(!legacy.contains("--demo"));
+    assert!(!legacy.contains("-cp"));
+
+    let version = common::legacy_version();
+    let arguments = Arguments::from_legacy(version.minecraft_arguments.as_deref().unwrap());
+    assert_eq!(
+        Some(arguments.to_legacy_string(&ctx)),
+        version.minecraft_arguments
+    );
+}
+
+#[test]
+fn non_string_value_elements() {
+    let err = serde_json::from_str::<Argument>(r#"{"rules": [], "value": ["--x", 5]}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("argument value array must contain only strings, found number at index 1"),
+        "{}",
+        err
+    );
+
+    let err = serde_json::from_str::<Argument>(r#"[{"a": 1}]"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("found object at index 0"), "{}", err);
+
+    let err = serde_json::from_str::<Arguments>(r#"{"game": ["--a", {"value": ["--b", null]}]}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("found null at index 1"), "{}", err);
+
+    let argument: Argument =
+        serde_json::from_str(r#"{"rules": [], "value": ["--x", "5"]}"#).unwrap();
+    assert_eq!(argument.values, ["--x", "5"]);
+}
+
+#[test]
+fn values_alias() {
+    let argument: Argument = serde_json::from_str(
+        r#"{"rules": [{"action": "allow", "features": {"has_custom_resolution": true}}],
+            "values": ["--width", "${resolution_width}"]}"#,
+    )
+    .unwrap();
+    assert_eq!(argument.values, ["--width", "${resolution_width}"]);
+    assert_eq!(argument.rules.len(), 1);
+
+    // still written as `value`
+    let json = serde_json::to_value(&argument).unwrap();
+    assert_eq!(
+        json["value"],
+        serde_json::json!(["--width", "${resolution_width}"])
+    );
+    assert!(json.get("values").is_none());
+
+    let argument: Argument = serde_json::from_str(r#"{"values": "--foo"}"#).unwrap();
+    assert_eq!(argument, "--foo".parse().unwrap());
+
+    let err =
+        serde_json::from_str::<Argument>(r#"{"value": "--foo", "values": "--bar"}"#).unwrap_err();
+    assert!(err.to_string().contains("duplicate field `value`"));
+}
+
+#[test]
+fn appended_arguments() {
+    let mut arguments = common::sample_version().arguments.unwrap();
+    let ctx = RuleContext::default();
+    let game = arguments.build_game(&ctx);
+    let jvm = arguments.build_jvm(&ctx);
+
+    arguments.push_jvm(Argument::literal("-Dfml.ignorePatchDiscrepancies=true"));
+    arguments.extend_jvm([
+        Argument::literal("-Dfml.ignoreInvalidMinecraftCertificates=true"),
+        Argument::conditional(["-Xdock:name=Forge"], vec![osx_rule()]),
+    ]);
+    arguments.push_game(Argument::conditional(
+        vec!["--launchTarget".to_owned(), "forgeclient".to_owned()],
+        vec![],
+    ));
+    arguments.extend_game(vec![Argument::literal("--fml.forgeVersion")]);
+
+    let built = arguments.build_jvm(&ctx);
+    assert_eq!(&built[..jvm.len()], jvm.as_slice());
+    assert_eq!(
+        &built[jvm.len()..],
+        [
+            "-Dfml.ignorePatchDiscrepancies=true",
+            "-Dfml.ignoreInvalidMinecraftCertificates=true"
+        ]
+    );
+    let osx = RuleContext {
+        os_name: Some(OsName::Osx),
+        ..RuleContext::default()
+    };
+    assert_eq!(
+        arguments.build_jvm(&osx).last().map(String::as_str),
+        Some("-Xdock:name=Forge")
+    );
+
+    let built = arguments.build_game(&ctx);
+    assert_eq!(&built[..game.len()], game.as_slice());
+    assert_eq!(
+        &built[game.len()..],
+        ["--launchTarget", "forgeclient", "--fml.forgeVersion"]
+    );
+
+    // a single conditional value is written like Mojang's
+    let json = serde_json::to_value(Argument::conditional(
+        ["-Xdock:name=Forge"],
+        vec![osx_rule()],
+    ))
+    .unwrap();
+    assert_eq!(json["value"], "-Xdock:name=Forge");
+    assert_eq!(Argument::literal("--demo"), "--demo".parse().unwrap());
+}
+
+fn osx_rule() -> Rule {
+    serde_json::from_str(r#"{"action": "allow", "os": {"name": "osx"}}"#).unwrap()
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use mc_launchermeta::asset_index::{AssetIndex, AssetLayout, SizeMismatch, DEFAULT_RESOURCES_URL};
+
+const OBJECTS: &str = r#"{
+    "objects": {
+        "icons/icon_16x16.png": {
+            "hash": "bdf48ef6b5d0d23bbb02e17d04865216179f510a",
+            "size": 3665
+        },
+        "minecraft/sounds/ambient/cave/cave1.ogg": {
+            "hash": "5a8bd2a2e2dc5f3d4a6e8a0b0b0e8c0f5d2a3b1c",
+            "size": 27814
+        }
+    }
+}"#;
+
+#[test]
+fn asset_objects() {
+    let index: AssetIndex = serde_json::from_str(OBJECTS).unwrap();
+    assert_eq!(index.objects.len(), 2);
+    assert_eq!(index.map_to_resources, None);
+    assert_eq!(index.r#virtual, None);
+
+    // modern indexes have neither flag, and don't gain them when written back
+    assert_eq!(
+        serde_json::to_value(&index).unwrap(),
+        serde_json::from_str::<serde_json::Value>(OBJECTS).unwrap()
+    );
+
+    let (name, object) = &index.objects[0];
+    assert_eq!(name, "icons/icon_16x16.png");
+    assert_eq!(object.size, 3665);
+    assert_eq!(object.path(), "bd/bdf48ef6b5d0d23bbb02e17d04865216179f510a");
+    assert_eq!(
+        object.url(DEFAULT_RESOURCES_URL),
+        "https://resources.download.minecraft.net/bd/bdf48ef6b5d0d23bbb02e17d04865216179f510a"
+    );
+    assert_eq!(
+        object.url("https://mirror.example.com/assets"),
+        "https://mirror.example.com/assets/bd/bdf48ef6b5d0d23bbb02e17d04865216179f510a"
+    );
+}
+
+#[test]
+fn legacy_layouts() {
+    const KEY: &str = "icons/icon_16x16.png";
+
+    let mut index: AssetIndex = serde_json::from_str(OBJECTS).unwrap();
+    assert!(!index.is_virtual());
+    assert_eq!(
+        index.destination(KEY).unwrap(),
+        "bd/bdf48ef6b5d0d23bbb02e17d04865216179f510a"
+    );
+    assert_eq!(index.destination("missing.png"), None);
+
+    let json = OBJECTS.replacen('{', r#"{"virtual": true,"#, 1);
+    index = serde_json::from_str(&json).unwrap();
+    assert!(index.is_virtual());
+    assert_eq!(serde_json::to_value(&index).unwrap()["virtual"], true);
+    assert_eq!(index.destination(KEY).unwrap(), KEY);
+
+    let json = OBJECTS.replacen('{', r#"{"map_to_resources": true,"#, 1);
+    index = serde_json::from_str(&json).unwrap();
+    assert!(index.maps_to_resources());
+    assert!(!index.is_virtual());
+    assert_eq!(index.destination(KEY).unwrap(), KEY);
+}
+
+#[test]
+fn verify_total_size() {
+    let objects: AssetIndex = serde_json::from_str(OBJECTS).unwrap();
+    let mut index = common::sample_version().asset_index;
+
+    index.total_size = 3665 + 27814;
+    assert_eq!(objects.verify_total(&index), Ok(()));
+
+    index.total_size = 626413221;
+    assert_eq!(
+        objects.verify_total(&index),
+        Err(SizeMismatch {
+            expected: 626413221,
+            actual: 31479
+        })
+    );
+}
+
+#[test]
+fn asset_layouts() {
+    let mut version = common::sample_version();
+    assert_eq!(version.assets_id(), "12");
+    assert_eq!(version.asset_layout(), AssetLayout::Hashed);
+
+    version.assets = "legacy".to_owned();
+    assert_eq!(version.asset_layout(), AssetLayout::Legacy);
+    version.assets = "pre-1.6".to_owned();
+    assert_eq!(version.asset_layout(), AssetLayout::Pre16);
+
+    version.assets.clear();
+    assert_eq!(version.assets_id(), version.asset_index.id);
+    assert_eq!(common::legacy_version().asset_layout(), AssetLayout::Hashed);
+}
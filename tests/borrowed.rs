@@ -0,0 +1,50 @@
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mc_launchermeta::version::borrowed::VersionRef;
+use mc_launchermeta::version::Version;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let value = f();
+    (value, ALLOCATIONS.load(Ordering::Relaxed) - before)
+}
+
+// everything is in one test, as other tests running in parallel would skew the counts
+#[test]
+fn borrowed_allocates_less() {
+    let (owned, owned_allocations) =
+        count_allocations(|| serde_json::from_str::<Version>(common::SAMPLE_VERSION).unwrap());
+    let (borrowed, borrowed_allocations) =
+        count_allocations(|| serde_json::from_str::<VersionRef>(common::SAMPLE_VERSION).unwrap());
+
+    assert!(borrowed
+        .libraries
+        .iter()
+        .all(|library| matches!(library.name, std::borrow::Cow::Borrowed(_))));
+    // the name, path and url of every library are no longer allocated
+    assert!(borrowed_allocations * 2 < owned_allocations);
+
+    assert_eq!(VersionRef::from(&owned), borrowed);
+    assert_eq!(borrowed.into_owned(), owned);
+}
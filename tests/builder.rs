@@ -0,0 +1,32 @@
+mod common;
+
+use mc_launchermeta::version::builder::BuildError;
+use mc_launchermeta::version::Version;
+
+#[test]
+fn build_minimal() {
+    let sample = common::sample_version();
+    let version = Version::builder()
+        .id("custom")
+        .main_class("net.minecraft.client.main.Main")
+        .asset_index(sample.asset_index.clone())
+        .client_download(sample.downloads.client.clone())
+        .build()
+        .unwrap();
+    assert_eq!(version.id, "custom");
+    assert_eq!(version.assets, "12");
+    assert!(version.libraries.is_empty());
+    assert!(version.logging.is_none());
+    assert_eq!(version.downloads.client, sample.downloads.client);
+}
+
+#[test]
+fn build_without_main_class() {
+    let sample = common::sample_version();
+    let result = Version::builder()
+        .id("custom")
+        .asset_index(sample.asset_index)
+        .downloads(sample.downloads)
+        .build();
+    assert_eq!(result, Err(BuildError::MissingField("main_class")));
+}
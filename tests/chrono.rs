@@ -0,0 +1,70 @@
+#![cfg(feature = "chrono")]
+
+mod common;
+
+use mc_launchermeta::chrono::{TimeZone, Utc};
+use mc_launchermeta::version_manifest::Manifest;
+
+#[test]
+fn parse_release_time() {
+    let version = common::sample_version();
+    let expected = Utc.with_ymd_and_hms(2023, 12, 7, 12, 56, 20).unwrap();
+    assert_eq!(version.released_at().unwrap(), expected);
+    assert_eq!(version.updated_at().unwrap(), expected);
+}
+
+#[test]
+fn sort_by_release() {
+    let entry = |id: &str, release_time: &str| {
+        serde_json::json!({
+            "id": id,
+            "type": "snapshot",
+            "url": format!("https://piston-meta.mojang.com/{}.json", id),
+            "time": release_time,
+            "releaseTime": release_time,
+        })
+    };
+    let manifest: Manifest = serde_json::from_value(serde_json::json!({
+        "latest": {"release": "1.20.4", "snapshot": "1.20.4"},
+        "versions": [
+            entry("23w51a", "2023-12-18T14:30:12+00:00"),
+            entry("1.20.3", "2023-12-04T12:10:32+00:00"),
+            entry("1.20.4-rc1", "2023-12-07T12:56:20+00:00"),
+            entry("1.20.4", "2023-12-07T12:56:20+00:00"),
+            entry("24w03a", "2024-01-17T13:48:12+00:00"),
+        ],
+    }))
+    .unwrap();
+
+    let ids: Vec<_> = manifest
+        .sorted_by_release()
+        .iter()
+        .map(|version| version.id.as_str())
+        .collect();
+    assert_eq!(ids, ["24w03a", "23w51a", "1.20.4-rc1", "1.20.4", "1.20.3"]);
+
+    let mut versions = manifest.versions.clone();
+    versions.sort();
+    assert_eq!(versions[0].id, "24w03a");
+    assert_eq!(versions[4].id, "1.20.3");
+    assert!(manifest.versions[4] < manifest.versions[0]);
+}
+
+#[test]
+fn tolerant_timestamps() {
+    let mut version = common::sample_version();
+    let expected = Utc.with_ymd_and_hms(2023, 12, 7, 12, 56, 20).unwrap();
+    for time in [
+        "2023-12-07T12:56:20+00:00",
+        "2023-12-07T12:56:20Z",
+        "2023-12-07T12:56:20",
+        "2023-12-07T12:56:20+0000",
+        "2023-12-07T13:56:20+01:00",
+    ] {
+        version.release_time = time.to_owned();
+        assert_eq!(version.released_at().unwrap(), expected, "{}", time);
+    }
+
+    version.release_time = "2023-12-07".to_owned();
+    assert!(version.released_at().is_err());
+}
@@ -0,0 +1,34 @@
+#![allow(dead_code)]
+
+use mc_launchermeta::version::Version;
+
+/// A modern version, using the `arguments` field and per-OS natives libraries
+pub const SAMPLE_VERSION: &str = include_str!("../data/1.20.4.json");
+
+pub fn sample_version() -> Version {
+    serde_json::from_str(SAMPLE_VERSION).unwrap()
+}
+
+/// A version from before the `arguments` field, using the old natives model
+pub const LEGACY_VERSION: &str = include_str!("../data/1.8.9.json");
+
+pub fn legacy_version() -> Version {
+    serde_json::from_str(LEGACY_VERSION).unwrap()
+}
+
+/// Parse some JSON, serialize it again and parse the result, checking nothing was lost
+///
+/// Both the parsed values and the JSON itself must match, ignoring formatting and key order.
+pub fn assert_round_trip<T>(json: &str)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let parsed: T = serde_json::from_str(json).unwrap();
+    let serialized = serde_json::to_string(&parsed).unwrap();
+    let reparsed: T = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(parsed, reparsed);
+
+    let original: serde_json::Value = serde_json::from_str(json).unwrap();
+    let serialized: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(original, serialized);
+}
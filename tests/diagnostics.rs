@@ -0,0 +1,26 @@
+#![cfg(feature = "diagnostics")]
+
+mod common;
+
+use mc_launchermeta::version::Version;
+
+#[test]
+fn error_path() {
+    assert_eq!(
+        Version::from_str_diagnostic(common::SAMPLE_VERSION).unwrap(),
+        common::sample_version()
+    );
+    let with_bom = format!("\u{feff}{}", common::SAMPLE_VERSION);
+    assert_eq!(
+        Version::from_str_diagnostic(&with_bom).unwrap(),
+        common::sample_version()
+    );
+
+    let mut json: serde_json::Value = serde_json::from_str(common::SAMPLE_VERSION).unwrap();
+    json["libraries"][23]["downloads"]["artifact"]["size"] = "foo".into();
+    let err = Version::from_str_diagnostic(&json.to_string()).unwrap_err();
+    assert_eq!(
+        err.path().to_string(),
+        "libraries[23].downloads.artifact.size"
+    );
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use mc_launchermeta::download::Downloadable;
+use mc_launchermeta::version::Download;
+
+#[test]
+fn downloadable_through_both_types() {
+    let version = common::sample_version();
+    let library = &version.libraries[4];
+    let artifact = library
+        .downloads
+        .as_ref()
+        .unwrap()
+        .artifact
+        .as_ref()
+        .unwrap();
+
+    let downloads: Vec<&dyn Downloadable> = vec![&version.downloads.client, artifact];
+    let names: Vec<_> = downloads.iter().map(|d| d.file_name().unwrap()).collect();
+    assert_eq!(names, ["client.jar", "guava-32.1.2-jre.jar"]);
+
+    assert_eq!(downloads[0].size(), version.downloads.client.size);
+    assert_eq!(*downloads[1].sha1(), artifact.sha1);
+    assert_eq!(downloads[1].url(), artifact.url);
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn verify_bytes() {
+    use mc_launchermeta::sha1::Sha1;
+    use mc_launchermeta::version::library::Artifact;
+
+    let data = b"hello world";
+    let download = Download {
+        sha1: Sha1::digest(data),
+        size: data.len() as u64,
+        url: "https://example.com/hello.txt".to_owned(),
+    };
+    let artifact = Artifact {
+        path: "hello.txt".to_owned(),
+        sha1: download.sha1,
+        size: download.size,
+        url: download.url.clone(),
+    };
+    for downloadable in [&download as &dyn Downloadable, &artifact] {
+        assert!(downloadable.verify_bytes(data));
+        assert!(!downloadable.verify_bytes(b"hello world!"));
+        assert!(!downloadable.verify_bytes(b"hello worle"));
+    }
+}
+
+#[test]
+fn asset_index_download() {
+    let version = common::sample_version();
+    let asset_index = &version.asset_index;
+    let download = asset_index.download();
+    assert_eq!(download.sha1, asset_index.sha1);
+    assert_eq!(download.size, asset_index.size);
+    assert_eq!(download.url, asset_index.url);
+    assert_eq!(Download::from(asset_index), download);
+
+    let downloads: Vec<&dyn Downloadable> = vec![asset_index, &download];
+    for downloadable in downloads {
+        assert_eq!(downloadable.file_name(), Some("12.json"));
+        assert_eq!(downloadable.size(), asset_index.size);
+    }
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn verify_asset_index_bytes() {
+    use mc_launchermeta::sha1::Sha1;
+    use mc_launchermeta::version::AssetIndex;
+
+    // an index as it would be fetched from the URL
+    let index = br#"{"objects": {"icons/icon_16x16.png": {"hash": "bdf48ef6b5d0d23bbb02e17d04865216179f510a", "size": 3665}}}"#;
+    let asset_index = AssetIndex {
+        id: "12".to_owned(),
+        sha1: Sha1::digest(index),
+        size: index.len() as u64,
+        total_size: 3665,
+        url: "https://piston-meta.mojang.com/v1/packages/0000/12.json".to_owned(),
+    };
+
+    for downloadable in [&asset_index as &dyn Downloadable, &asset_index.download()] {
+        assert!(downloadable.verify_bytes(index));
+        assert!(downloadable.verify(&index[1..]).is_err());
+    }
+    let mut tampered = index.to_vec();
+    tampered[2] = b'X';
+    assert!(asset_index.verify(&tampered).is_err());
+}
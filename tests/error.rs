@@ -0,0 +1,84 @@
+mod common;
+
+use mc_launchermeta::version::maven::MavenCoord;
+use mc_launchermeta::version::Version;
+use mc_launchermeta::version_manifest::Manifest;
+use mc_launchermeta::Error;
+
+#[test]
+fn parse_errors() {
+    assert!(matches!(Version::from_str_json("{}"), Err(Error::Parse(_))));
+    assert!(matches!(Manifest::from_slice(b"[]"), Err(Error::Parse(_))));
+}
+
+#[test]
+fn coordinate_errors() {
+    let err = Error::from("com.example".parse::<MavenCoord>().unwrap_err());
+    assert!(matches!(err, Error::Coordinate(_)));
+    assert_eq!(
+        err.to_string(),
+        "invalid maven coordinate: missing artifact"
+    );
+}
+
+#[test]
+fn build_errors() {
+    let err = Error::from(Version::builder().build().unwrap_err());
+    assert!(matches!(err, Error::Build(_)));
+}
+
+#[test]
+fn validation_errors() {
+    let mut version = common::sample_version();
+    version.main_class.clear();
+    let err = Error::from(version.validate().unwrap_err());
+    assert!(matches!(&err, Error::Validation(errors) if errors.len() == 1));
+    assert_eq!(err.to_string(), "version has 1 validation errors");
+}
+
+#[test]
+fn asset_size_errors() {
+    use mc_launchermeta::asset_index::AssetIndex;
+    use mc_launchermeta::VerifyError;
+
+    let objects: AssetIndex = serde_json::from_str(include_str!("data/objects.json")).unwrap();
+    let mut index = common::sample_version().asset_index;
+    index.total_size = objects.total_size() + 1;
+    let err = Error::from(objects.verify_total(&index).unwrap_err());
+    assert!(matches!(
+        err,
+        Error::Verify(VerifyError::Size { expected, actual }) if expected == actual + 1
+    ));
+}
+
+#[cfg(feature = "verify")]
+#[test]
+fn verify_errors() {
+    use mc_launchermeta::download::Downloadable;
+    use mc_launchermeta::sha1::Sha1;
+    use mc_launchermeta::version::Download;
+    use mc_launchermeta::VerifyError;
+
+    let data = b"hello world";
+    let download = Download {
+        sha1: Sha1::digest(data),
+        size: data.len() as u64,
+        url: "https://example.com/hello.txt".to_owned(),
+    };
+    assert!(download.verify(data).is_ok());
+    assert_eq!(
+        download.verify(b"hello world!"),
+        Err(VerifyError::Size {
+            expected: 11,
+            actual: 12
+        })
+    );
+    assert!(matches!(
+        download.verify(b"hello worle"),
+        Err(VerifyError::Hash { expected, .. }) if expected == download.sha1
+    ));
+
+    // converts into the crate error with `?`
+    let err = Error::from(download.verify(b"").unwrap_err());
+    assert!(matches!(err, Error::Verify(VerifyError::Size { .. })));
+}
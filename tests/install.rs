@@ -0,0 +1,73 @@
+#![cfg(all(feature = "std", feature = "verify", feature = "rayon"))]
+
+mod common;
+
+use mc_launchermeta::sha1::Sha1;
+use mc_launchermeta::version::install::FileStatus;
+use mc_launchermeta::version::rule::{OsName, RuleContext};
+
+#[test]
+fn verify_installation() {
+    let good = b"good jar";
+    let corrupt = b"corrupt jar";
+    let mut version = common::sample_version();
+    version.libraries = version.libraries[1..4].to_vec();
+    for (library, data) in version.libraries.iter_mut().zip([good, good]) {
+        let artifact = library
+            .downloads
+            .as_mut()
+            .unwrap()
+            .artifact
+            .as_mut()
+            .unwrap();
+        artifact.sha1 = Sha1::digest(data);
+        artifact.size = data.len() as u64;
+    }
+    version.downloads.client.sha1 = Sha1::digest(good);
+
+    let dir = tempfile::tempdir().unwrap();
+    let libraries_dir = dir.path().join("libraries");
+    let write = |library: usize, data: &[u8]| {
+        let artifact = version.libraries[library]
+            .downloads
+            .as_ref()
+            .unwrap()
+            .artifact
+            .as_ref()
+            .unwrap();
+        let path = libraries_dir.join(artifact.safe_relative_path().unwrap());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, data).unwrap();
+    };
+    write(0, good);
+    write(1, corrupt);
+    let client_jar = dir.path().join("client.jar");
+    std::fs::write(&client_jar, good).unwrap();
+
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+    let reports = version
+        .verify_installation(&ctx, &libraries_dir, &client_jar)
+        .unwrap();
+    let statuses: Vec<_> = reports
+        .iter()
+        .map(|report| (report.coordinate.as_str(), report.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            (version.libraries[0].name.as_str(), FileStatus::Ok),
+            (version.libraries[1].name.as_str(), FileStatus::Mismatch),
+            (version.libraries[2].name.as_str(), FileStatus::Missing),
+            ("client", FileStatus::Ok),
+        ]
+    );
+    assert!(reports[0].is_ok());
+    assert_eq!(reports[1].expected, Sha1::digest(good));
+    assert_eq!(reports[1].actual, Some(Sha1::digest(corrupt)));
+    assert_eq!(reports[2].actual, None);
+    assert_eq!(reports[3].path, client_jar);
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use mc_launchermeta::java_runtime::JavaRuntimeIndex;
+
+const JAVA_RUNTIMES: &str = include_str!("data/java_runtime.json");
+
+#[test]
+fn runtime_index() {
+    let index: JavaRuntimeIndex = serde_json::from_str(JAVA_RUNTIMES).unwrap();
+    assert_eq!(index.0.len(), 3);
+    assert!(index.get("gamecore", "jre-legacy").is_none());
+    assert!(index.get("windows-x64", "jre-legacy").is_none());
+    assert!(index.get("mac-os", "java-runtime-gamma").is_none());
+
+    let java_version = common::sample_version().required_java();
+    let runtime = index.for_java_version("linux", &java_version).unwrap();
+    assert_eq!(runtime.version.name, "17.0.8");
+    assert_eq!(runtime.manifest.size, 127241);
+    assert_eq!(
+        runtime.manifest.sha1.to_string(),
+        "3b3a1a8c0b8b1e2a4b6d3f5e1c9c4e3d2a1b0c9d"
+    );
+
+    let legacy = index.get("linux", "jre-legacy").unwrap();
+    assert_eq!(legacy.version.name, "8u51");
+}
+
+#[test]
+fn runtime_index_round_trip() {
+    common::assert_round_trip::<JavaRuntimeIndex>(JAVA_RUNTIMES);
+}
@@ -0,0 +1,758 @@
+mod common;
+
+use mc_launchermeta::download::Downloadable;
+use mc_launchermeta::version::borrowed::LibraryRef;
+use mc_launchermeta::version::library::{
+    dedup_libraries,
+    native_classifier_chain,
+    resolve_natives_key,
+    Extract,
+    Library,
+    LibrarySet,
+    Natives,
+};
+use mc_launchermeta::version::rule::{OsName, RuleContext};
+#[cfg(feature = "std")]
+use mc_launchermeta::version::stream::stream_libraries;
+
+#[test]
+fn artifact_file_name() {
+    let version = common::sample_version();
+    let names: Vec<_> = version
+        .libraries
+        .iter()
+        .filter(|library| library.name.starts_with("org.lwjgl:lwjgl-glfw:"))
+        .map(|library| {
+            let artifact = library.downloads.as_ref().unwrap().artifact.as_ref();
+            artifact.unwrap().file_name().unwrap()
+        })
+        .collect();
+    assert_eq!(names[0], "lwjgl-glfw-3.3.2.jar");
+    assert!(names.contains(&"lwjgl-glfw-3.3.2-natives-macos-arm64.jar"));
+}
+
+#[test]
+fn extract_exclusions() {
+    let extract: Extract = serde_json::from_str(r#"{"exclude": ["META-INF/"]}"#).unwrap();
+    assert!(extract.should_exclude("META-INF/MANIFEST.MF"));
+    assert!(extract.should_exclude("META-INF/"));
+    assert!(!extract.should_exclude("liblwjgl.so"));
+    assert!(!extract.should_exclude("META-INF-extra/file.txt"));
+
+    let extract: Extract = serde_json::from_str(r#"{"exclude": ["META-INF"]}"#).unwrap();
+    assert!(extract.should_exclude("META-INF/MANIFEST.MF"));
+    assert!(!extract.should_exclude("windows/x64/lwjgl.dll"));
+
+    assert!(!Extract::default().should_exclude("META-INF/MANIFEST.MF"));
+
+    // prefixes don't have to be directories
+    let extract: Extract = serde_json::from_str(r#"{"exclude": ["META-INF/MANIFEST"]}"#).unwrap();
+    assert!(extract.should_exclude("META-INF/MANIFEST.MF"));
+    assert!(extract.should_exclude("META-INF/MANIFEST"));
+    assert!(!extract.should_exclude("META-INF/INDEX.LIST"));
+}
+
+#[test]
+fn extract_fields() {
+    let extract: Extract = serde_json::from_str(r#"{"exclude": ["META-INF/"]}"#).unwrap();
+    assert_eq!(extract.exclude, ["META-INF/"]);
+    assert!(extract.other.is_empty());
+    assert_eq!(
+        serde_json::to_value(&extract).unwrap(),
+        serde_json::json!({"exclude": ["META-INF/"]})
+    );
+
+    let extract: Extract = serde_json::from_str("{}").unwrap();
+    assert_eq!(extract, Extract::default());
+    assert!(!extract.should_exclude("META-INF/MANIFEST.MF"));
+    assert_eq!(serde_json::to_string(&extract).unwrap(), "{}");
+
+    // other keys are kept, but don't exclude anything
+    let json = serde_json::json!({"exclude": ["META-INF/"], "include": ["natives/"]});
+    let extract: Extract = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(extract.other["include"], ["natives/"]);
+    assert!(!extract.should_exclude("natives/lwjgl.dll"));
+    assert_eq!(serde_json::to_value(&extract).unwrap(), json);
+}
+
+fn guava(version: &str) -> Library {
+    Library::new(format!("com.google.guava:guava:{}", version))
+}
+
+#[test]
+fn dedup_keeps_last() {
+    let libraries = common::sample_version().libraries;
+    let mut with_duplicates = vec![guava("31.1-jre")];
+    with_duplicates.extend(libraries.iter().cloned());
+    with_duplicates.push(guava("30.0-jre"));
+
+    let deduped = dedup_libraries(&with_duplicates);
+    let mut expected = libraries.clone();
+    expected.retain(|library| !library.name.starts_with("com.google.guava:guava:"));
+    expected.push(guava("30.0-jre"));
+    assert_eq!(deduped, expected);
+}
+
+#[cfg(feature = "semver")]
+#[test]
+fn dedup_keeps_highest() {
+    use mc_launchermeta::version::library::{dedup_libraries_by, DedupPolicy};
+
+    let libraries = vec![guava("32.1.2-jre"), guava("31.1.0-jre")];
+    let deduped = dedup_libraries_by(&libraries, DedupPolicy::HighestVersion);
+    assert_eq!(deduped, [guava("32.1.2-jre")]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_matches_full_parse() {
+    let libraries: Vec<_> = stream_libraries(common::SAMPLE_VERSION.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(libraries.len(), 88);
+    assert_eq!(libraries, common::sample_version().libraries);
+
+    let mut stream = stream_libraries(&br#"{"id": "x", "libraries": [{"name": 5}]}"#[..]);
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+    assert!(stream_libraries(&br#"{"libraries": []}"#[..])
+        .next()
+        .is_none());
+}
+
+#[test]
+fn natives_arch_substitution() {
+    assert_eq!(
+        resolve_natives_key("natives-windows-${arch}", 32),
+        "natives-windows-32"
+    );
+    assert_eq!(
+        resolve_natives_key("natives-windows-${arch}", 64),
+        "natives-windows-64"
+    );
+    assert_eq!(resolve_natives_key("natives-linux", 64), "natives-linux");
+
+    let version = common::legacy_version();
+    let twitch = version
+        .libraries
+        .iter()
+        .find(|library| library.name == "tv.twitch:twitch-platform:6.5")
+        .unwrap();
+    let mut ctx = RuleContext {
+        os_name: Some(OsName::Windows),
+        arch: Some("x86".to_owned()),
+        ..RuleContext::default()
+    };
+    let artifact = twitch.native_artifact(&ctx).unwrap();
+    assert!(artifact
+        .path
+        .ends_with("twitch-platform-6.5-natives-windows-32.jar"));
+    ctx.arch = Some("x86_64".to_owned());
+    let artifact = twitch.native_artifact(&ctx).unwrap();
+    assert!(artifact
+        .path
+        .ends_with("twitch-platform-6.5-natives-windows-64.jar"));
+    ctx.os_name = Some(OsName::Linux);
+    assert!(twitch.native_artifact(&ctx).is_none());
+}
+
+#[test]
+fn classifier_lookup() {
+    let version = common::legacy_version();
+    let find = |name: &str| {
+        version
+            .libraries
+            .iter()
+            .find(|library| library.name == name)
+            .unwrap()
+    };
+
+    let lwjgl = find("org.lwjgl.lwjgl:lwjgl-platform:2.9.4-nightly-20150209");
+    assert!(lwjgl
+        .classifier("natives-windows")
+        .unwrap()
+        .path
+        .ends_with("lwjgl-platform-2.9.4-nightly-20150209-natives-windows.jar"));
+    assert!(lwjgl.classifier("natives-windows-64").is_none());
+
+    let twitch = find("tv.twitch:twitch-platform:6.5");
+    assert!(twitch
+        .classifier(&resolve_natives_key("natives-windows-${arch}", 64))
+        .unwrap()
+        .path
+        .ends_with("twitch-platform-6.5-natives-windows-64.jar"));
+    assert!(twitch.classifier("natives-windows").is_none());
+}
+
+#[test]
+fn native_artifact_unresolved_key() {
+    let library: Library = serde_json::from_value(serde_json::json!({
+        "name": "com.example:natives:1.0",
+        "natives": {"windows": "natives-windows-${arch}"},
+        "downloads": {
+            "classifiers": {
+                "natives-windows-${arch}": {
+                    "path": "com/example/natives/1.0/natives-1.0-natives-windows.jar",
+                    "sha1": "0000000000000000000000000000000000000000",
+                    "size": 1,
+                    "url": "https://example.com/natives-1.0-natives-windows.jar",
+                },
+            },
+        },
+    }))
+    .unwrap();
+    let ctx = RuleContext {
+        os_name: Some(OsName::Windows),
+        ..RuleContext::default()
+    };
+    assert_eq!(
+        library.native_artifact(&ctx),
+        library.classifier("natives-windows-${arch}")
+    );
+    assert!(library.native_artifact(&ctx).is_some());
+}
+
+#[test]
+fn natives_for_both_models() {
+    let linux = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..RuleContext::default()
+    };
+    let osx = RuleContext {
+        os_name: Some(OsName::Osx),
+        ..RuleContext::default()
+    };
+
+    let modern = common::sample_version().libraries;
+    let find = |name: &str| modern.iter().find(|library| library.name == name).unwrap();
+    let glfw_macos = find("org.lwjgl:lwjgl-glfw:3.3.2:natives-macos");
+    let natives = glfw_macos.natives_for(&osx);
+    assert_eq!(natives.len(), 1);
+    assert!(natives[0]
+        .path
+        .ends_with("lwjgl-glfw-3.3.2-natives-macos.jar"));
+    assert!(glfw_macos.natives_for(&linux).is_empty());
+    assert!(find("org.lwjgl:lwjgl-glfw:3.3.2")
+        .natives_for(&osx)
+        .is_empty());
+
+    let legacy = common::legacy_version().libraries;
+    let platform = legacy
+        .iter()
+        .find(|library| library.name == "org.lwjgl.lwjgl:lwjgl-platform:2.9.4-nightly-20150209")
+        .unwrap();
+    let natives = platform.natives_for(&linux);
+    assert_eq!(natives.len(), 1);
+    assert!(natives[0].path.ends_with("natives-linux.jar"));
+    // this version of lwjgl is disallowed on osx
+    assert!(platform.natives_for(&osx).is_empty());
+}
+
+#[test]
+fn maven_only_library_url() {
+    let library: Library = serde_json::from_str(
+        r#"{
+            "name": "net.minecraftforge:forge:1.20.4-49.0.3:universal",
+            "url": "https://maven.minecraftforge.net"
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        library.resolved_url().as_deref(),
+        Some(
+            "https://maven.minecraftforge.net/net/minecraftforge/forge/1.20.4-49.0.3/\
+             forge-1.20.4-49.0.3-universal.jar"
+        )
+    );
+
+    let library: Library = serde_json::from_str(r#"{"name": "org.ow2.asm:asm:9.6"}"#).unwrap();
+    assert_eq!(
+        library.resolved_url().as_deref(),
+        Some("https://libraries.minecraft.net/org/ow2/asm/asm/9.6/asm-9.6.jar")
+    );
+
+    let version = common::sample_version();
+    let guava = &version.libraries[4];
+    assert_eq!(
+        guava.resolved_url().as_deref(),
+        Some(
+            guava
+                .downloads
+                .as_ref()
+                .unwrap()
+                .artifact
+                .as_ref()
+                .unwrap()
+                .url
+                .as_str()
+        )
+    );
+}
+
+#[test]
+fn same_artifact_ignores_host() {
+    let library = common::sample_version().libraries[4].clone();
+    let mut mirrored = library.clone();
+    if let Some(artifact) = mirrored
+        .downloads
+        .as_mut()
+        .and_then(|d| d.artifact.as_mut())
+    {
+        artifact.url = artifact
+            .url
+            .replace("libraries.minecraft.net", "mirror.example.com");
+    }
+    assert_ne!(library, mirrored);
+    assert!(library.same_artifact(&mirrored));
+
+    let mut other_hash = mirrored.clone();
+    if let Some(artifact) = other_hash
+        .downloads
+        .as_mut()
+        .and_then(|d| d.artifact.as_mut())
+    {
+        artifact.sha1 = "0000000000000000000000000000000000000000".parse().unwrap();
+    }
+    assert!(!library.same_artifact(&other_hash));
+    assert!(!library.same_artifact(&guava("31.1-jre")));
+}
+
+#[test]
+fn natives_for_prefers_arch() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Osx),
+        arch: Some("aarch64".to_owned()),
+        ..RuleContext::default()
+    };
+    let version = common::sample_version();
+    let natives: Vec<_> = version
+        .libraries
+        .iter()
+        .filter(|library| library.name.starts_with("org.lwjgl:lwjgl-glfw:"))
+        .flat_map(|library| library.natives_for(&ctx))
+        .collect();
+    assert_eq!(natives.len(), 1);
+    assert!(natives[0]
+        .path
+        .ends_with("lwjgl-glfw-3.3.2-natives-macos-arm64.jar"));
+}
+
+#[test]
+fn natives_for_os() {
+    let natives: Natives = serde_json::from_str(
+        r#"{"osx": "natives-osx", "windows": "natives-windows-${arch}", "solaris": "x"}"#,
+    )
+    .unwrap();
+    assert_eq!(natives.for_os(&OsName::Osx), Some("natives-osx"));
+    assert_eq!(
+        natives.for_os(&OsName::Windows),
+        Some("natives-windows-${arch}")
+    );
+    assert_eq!(natives.for_os(&OsName::Linux), None);
+
+    let json = serde_json::to_value(&natives).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"osx": "natives-osx", "windows": "natives-windows-${arch}"})
+    );
+}
+
+#[test]
+fn allowed_on_os() {
+    let libraries = common::sample_version().libraries;
+    let find = |prefix: &str| {
+        libraries
+            .iter()
+            .find(|library| library.name.starts_with(prefix))
+            .unwrap()
+    };
+
+    let objc_bridge = find("ca.weblite:java-objc-bridge:");
+    assert!(objc_bridge.is_allowed_on(OsName::Osx));
+    assert!(!objc_bridge.is_allowed_on(OsName::Linux));
+    assert!(!objc_bridge.is_allowed_on(OsName::Windows));
+
+    let epoll = find("io.netty:netty-transport-native-epoll:");
+    assert!(epoll.is_allowed_on(OsName::Linux));
+    assert!(!epoll.is_allowed_on(OsName::Osx));
+    assert!(!epoll.is_allowed_on(OsName::Windows));
+
+    let guava = find("com.google.guava:guava:");
+    assert!(guava.is_allowed_on(OsName::Windows));
+}
+
+#[test]
+fn rules_empty_object() {
+    let parse = |rules: serde_json::Value| {
+        serde_json::from_value::<Library>(serde_json::json!({
+            "name": "com.example:example:1.0",
+            "rules": rules,
+        }))
+    };
+    assert_eq!(parse(serde_json::json!({})).unwrap().rules, None);
+    assert_eq!(parse(serde_json::json!(null)).unwrap().rules, None);
+    assert_eq!(parse(serde_json::json!([])).unwrap().rules, Some(vec![]));
+    let rules = parse(serde_json::json!([{"action": "allow"}]))
+        .unwrap()
+        .rules
+        .unwrap();
+    assert_eq!(rules.len(), 1);
+    assert!(parse(serde_json::json!({"action": "allow"})).is_err());
+
+    let library: LibraryRef =
+        serde_json::from_str(r#"{"name": "com.example:example:1.0", "rules": {}}"#).unwrap();
+    assert_eq!(library.rules, None);
+}
+
+#[test]
+fn library_set() {
+    let version = common::sample_version();
+    let set = version.library_set();
+    assert_eq!(set.len(), version.libraries.len());
+
+    let lwjgl: Vec<_> = set
+        .by_coordinate("org.lwjgl", "lwjgl")
+        .map(|library| library.name.as_str())
+        .collect();
+    assert_eq!(lwjgl.len(), 7);
+    assert!(lwjgl.contains(&"org.lwjgl:lwjgl:3.3.2"));
+    assert!(lwjgl.contains(&"org.lwjgl:lwjgl:3.3.2:natives-linux"));
+    assert_eq!(set.by_coordinate("org.lwjgl", "missing").count(), 0);
+
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+    let applicable = set.applicable(&ctx);
+    assert!(applicable.len() < set.len());
+    assert!(applicable.iter().all(|library| library.applies_to(&ctx)));
+    assert_eq!(
+        applicable.by_coordinate("org.lwjgl", "lwjgl").count(),
+        2,
+        "the library and its linux natives"
+    );
+    assert_eq!(applicable.dedup(), applicable);
+
+    let names: Vec<_> = (&applicable)
+        .into_iter()
+        .map(|library| &library.name)
+        .collect();
+    assert_eq!(names.len(), applicable.len());
+    let collected: LibrarySet = applicable.clone().into_iter().collect();
+    assert_eq!(collected, applicable);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn safe_relative_path() {
+    use std::path::PathBuf;
+
+    use mc_launchermeta::version::library::UnsafePathError;
+
+    let version = common::sample_version();
+    let mut artifact = version.libraries[4]
+        .downloads
+        .clone()
+        .unwrap()
+        .artifact
+        .unwrap();
+    assert_eq!(
+        artifact.safe_relative_path(),
+        Ok(PathBuf::from("com")
+            .join("google")
+            .join("guava")
+            .join("guava")
+            .join("32.1.2-jre")
+            .join("guava-32.1.2-jre.jar"))
+    );
+
+    for (path, expected) in [
+        (
+            "./com//example\\example.jar",
+            Ok(PathBuf::from("com").join("example").join("example.jar")),
+        ),
+        ("../../etc/passwd", Err(UnsafePathError::ParentDir)),
+        ("com/../../outside.jar", Err(UnsafePathError::ParentDir)),
+        ("/etc/passwd", Err(UnsafePathError::Absolute)),
+        (
+            "\\\\server\\share\\file.jar",
+            Err(UnsafePathError::Absolute),
+        ),
+        ("C:\\Windows\\evil.dll", Err(UnsafePathError::Prefix)),
+        ("C:evil.dll", Err(UnsafePathError::Prefix)),
+        ("", Err(UnsafePathError::Empty)),
+        ("./", Err(UnsafePathError::Empty)),
+    ] {
+        artifact.path = path.to_owned();
+        assert_eq!(artifact.safe_relative_path(), expected, "{}", path);
+    }
+}
+
+#[test]
+fn best_native_fallback() {
+    let artifact = |classifier: &str| {
+        serde_json::json!({
+            "path": format!("com/example/natives/1.0/natives-1.0-{}.jar", classifier),
+            "sha1": "0000000000000000000000000000000000000000",
+            "size": 1,
+            "url": format!("https://example.com/natives-1.0-{}.jar", classifier),
+        })
+    };
+    let library: Library = serde_json::from_value(serde_json::json!({
+        "name": "com.example:natives:1.0",
+        "natives": {"windows": "natives-windows", "osx": "natives-osx"},
+        "downloads": {
+            "classifiers": {
+                "natives-windows": artifact("natives-windows"),
+                "natives-osx": artifact("natives-osx"),
+            },
+        },
+    }))
+    .unwrap();
+    let ctx = |os_name, arch: &str| {
+        RuleContext {
+            os_name: Some(os_name),
+            arch: Some(arch.to_owned()),
+            ..RuleContext::default()
+        }
+    };
+
+    let windows_arm = ctx(OsName::Windows, "aarch64");
+    assert_eq!(windows_arm.native_classifier(), "natives-windows-arm64");
+    assert_eq!(
+        library.best_native(&windows_arm),
+        library.classifier("natives-windows")
+    );
+    assert_eq!(
+        library.best_native(&ctx(OsName::Osx, "aarch64")),
+        library.classifier("natives-osx")
+    );
+    assert_eq!(library.best_native(&ctx(OsName::Linux, "x86_64")), None);
+
+    let mut library = library;
+    let arm64 = serde_json::from_value(artifact("natives-windows-arm64")).unwrap();
+    if let Some(classifiers) = library
+        .downloads
+        .as_mut()
+        .and_then(|d| d.classifiers.as_mut())
+    {
+        classifiers.insert("natives-windows-arm64".to_owned(), arm64);
+    }
+    assert_eq!(
+        library.best_native(&windows_arm),
+        library.classifier("natives-windows-arm64")
+    );
+    assert!(library.best_native(&windows_arm).is_some());
+
+    // natives of a library the rules leave out aren't used, like with `natives_for`
+    library.rules = serde_json::from_value(serde_json::json!([
+        {"action": "allow"},
+        {"action": "disallow", "os": {"name": "osx"}},
+    ]))
+    .unwrap();
+    assert_eq!(library.best_native(&ctx(OsName::Osx, "aarch64")), None);
+    assert_eq!(
+        library.best_native(&windows_arm),
+        library.classifier("natives-windows-arm64")
+    );
+
+    assert_eq!(
+        native_classifier_chain("natives-macos-arm64"),
+        ["natives-macos-arm64", "natives-macos", "natives-osx"]
+    );
+    assert_eq!(native_classifier_chain("natives-linux"), ["natives-linux"]);
+}
+
+#[test]
+fn natives_libraries() {
+    let windows = RuleContext {
+        os_name: Some(OsName::Windows),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+
+    let version = common::sample_version();
+    let natives = version.natives_libraries(&windows);
+    assert_eq!(natives.len(), 7);
+    for (library, artifact) in &natives {
+        assert!(library.name.ends_with(":natives-windows"));
+        assert!(artifact.path.ends_with("-natives-windows.jar"));
+    }
+
+    let legacy = common::legacy_version();
+    let natives = legacy.natives_libraries(&windows);
+    assert!(!natives.is_empty());
+    for (library, artifact) in &natives {
+        assert!(library.natives.is_some());
+        assert!(artifact.path.contains("natives-windows"));
+    }
+    assert!(natives.iter().any(|(library, _)| library.extract.is_some()));
+}
+
+#[test]
+fn arm_mac_natives_fallback() {
+    let mac = |arch: &str| {
+        RuleContext {
+            os_name: Some(OsName::Osx),
+            arch: Some(arch.to_owned()),
+            ..RuleContext::default()
+        }
+    };
+    let arm = mac("aarch64");
+    assert!(arm.is_arm_mac());
+    assert!(!mac("x86_64").is_arm_mac());
+
+    let version = common::sample_version();
+    assert!(!version.requires_x64_java_on_arm_mac());
+    let natives = version.natives_libraries(&arm);
+    assert!(!natives.is_empty());
+    assert!(natives
+        .iter()
+        .all(|(library, _)| library.name.ends_with(":natives-macos-arm64")));
+
+    // a version from before arm64 natives, which only has the x86_64 ones
+    let mut old = version.clone();
+    old.libraries
+        .retain(|library| !library.name.ends_with(":natives-macos-arm64"));
+    assert!(old.requires_x64_java_on_arm_mac());
+    let natives = old.natives_libraries(&arm);
+    assert_eq!(natives, old.natives_libraries(&mac("x86_64")));
+    assert!(!natives.is_empty());
+    assert!(natives
+        .iter()
+        .all(|(library, _)| library.name.ends_with(":natives-macos")));
+    assert_eq!(
+        old.artifacts(&arm).collect::<Vec<_>>(),
+        old.artifacts(&mac("x86_64")).collect::<Vec<_>>()
+    );
+
+    // the old natives model, with a single classifier for macOS
+    let legacy = common::legacy_version();
+    assert!(legacy.requires_x64_java_on_arm_mac());
+    let natives = legacy.natives_libraries(&arm);
+    assert!(!natives.is_empty());
+    assert!(natives
+        .iter()
+        .all(|(_, artifact)| artifact.path.contains("natives-osx")));
+}
+
+#[test]
+fn rewrite_download_url() {
+    let artifact = |file: &str| {
+        serde_json::json!({
+            "path": format!("org/lwjgl/lwjgl/3.2.2/{}", file),
+            "sha1": "0000000000000000000000000000000000000000",
+            "size": 1,
+            "url": format!("https://libraries.minecraft.net/org/lwjgl/lwjgl/3.2.2/{}", file),
+        })
+    };
+    let mut library: Library = serde_json::from_value(serde_json::json!({
+        "name": "org.lwjgl:lwjgl:3.2.2",
+        "url": "https://libraries.minecraft.net/",
+        "natives": {"linux": "natives-linux", "windows": "natives-windows"},
+        "downloads": {
+            "artifact": artifact("lwjgl-3.2.2.jar"),
+            "classifiers": {
+                "natives-linux": artifact("lwjgl-3.2.2-natives-linux.jar"),
+                "natives-windows": artifact("lwjgl-3.2.2-natives-windows.jar"),
+            },
+        },
+    }))
+    .unwrap();
+    let mirror = |url: &str| {
+        url.strip_prefix("https://libraries.minecraft.net/")
+            .map(|path| format!("https://mirror.example.com/{}", path))
+    };
+
+    assert_eq!(library.rewrite_download_url(mirror), 3);
+    let downloads = library.downloads.as_ref().unwrap();
+    assert_eq!(
+        downloads.artifact.as_ref().unwrap().url,
+        "https://mirror.example.com/org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2.jar"
+    );
+    assert_eq!(
+        library.classifier("natives-windows").unwrap().url,
+        "https://mirror.example.com/org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2-natives-windows.jar"
+    );
+    assert!(downloads
+        .classifiers
+        .as_ref()
+        .unwrap()
+        .values()
+        .all(|artifact| artifact.url.starts_with("https://mirror.example.com/")));
+    // the maven repository isn't a download
+    assert_eq!(
+        library.url.as_deref(),
+        Some("https://libraries.minecraft.net/")
+    );
+
+    // already rewritten, so nothing matches
+    assert_eq!(library.rewrite_download_url(mirror), 0);
+    let mut name_only: Library = serde_json::from_str(r#"{"name": "com.foo:bar:1.0"}"#).unwrap();
+    assert_eq!(name_only.rewrite_download_url(|_| Some(String::new())), 0);
+}
+
+#[test]
+fn classifiers_sorted() {
+    let artifact = |classifier: &str| {
+        serde_json::json!({
+            "path": format!("org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2-{}.jar", classifier),
+            "sha1": "0000000000000000000000000000000000000000",
+            "size": 1,
+            "url": format!("https://example.com/lwjgl-3.2.2-{}.jar", classifier),
+        })
+    };
+    let library: Library = serde_json::from_str(&format!(
+        r#"{{"name": "org.lwjgl:lwjgl:3.2.2", "downloads": {{"classifiers": {{
+            "natives-windows": {},
+            "javadoc": {},
+            "natives-linux": {},
+            "natives-macos": {}
+        }}}}}}"#,
+        artifact("natives-windows"),
+        artifact("javadoc"),
+        artifact("natives-linux"),
+        artifact("natives-macos"),
+    ))
+    .unwrap();
+
+    let classifiers: Vec<_> = library.classifiers().collect();
+    assert_eq!(
+        classifiers
+            .iter()
+            .map(|(classifier, _)| *classifier)
+            .collect::<Vec<_>>(),
+        [
+            "javadoc",
+            "natives-linux",
+            "natives-macos",
+            "natives-windows"
+        ]
+    );
+    for (classifier, artifact) in classifiers {
+        assert!(artifact.path.ends_with(&format!("-{}.jar", classifier)));
+    }
+
+    let name_only: Library = serde_json::from_str(r#"{"name": "com.foo:bar:1.0"}"#).unwrap();
+    assert_eq!(name_only.classifiers().count(), 0);
+    let version = common::sample_version();
+    assert!(version
+        .libraries
+        .iter()
+        .all(|library| library.classifiers().count() == 0));
+}
+
+#[test]
+fn name_only_library() {
+    let library: Library = serde_json::from_str(r#"{"name": "com.foo:bar:1.0"}"#).unwrap();
+    assert_eq!(
+        library.resolved_url().as_deref(),
+        Some("https://libraries.minecraft.net/com/foo/bar/1.0/bar-1.0.jar")
+    );
+    assert_eq!(
+        library.artifact_path().as_deref(),
+        Some("com/foo/bar/1.0/bar-1.0.jar")
+    );
+    assert!(!library.is_native());
+}
@@ -0,0 +1,93 @@
+use mc_launchermeta::version_manifest::Manifest;
+use mc_launchermeta::VersionKind;
+
+#[test]
+fn version_kinds() {
+    for (json, kind) in [
+        ("\"release\"", VersionKind::Release),
+        ("\"snapshot\"", VersionKind::Snapshot),
+        ("\"old_beta\"", VersionKind::OldBeta),
+        ("\"old_alpha\"", VersionKind::OldAlpha),
+        ("\"pending\"", VersionKind::Other("pending".to_owned())),
+    ] {
+        assert_eq!(serde_json::from_str::<VersionKind>(json).unwrap(), kind);
+        assert_eq!(serde_json::to_string(&kind).unwrap(), json);
+    }
+}
+
+#[test]
+fn version_kind_predicates() {
+    let kinds = [
+        VersionKind::Release,
+        VersionKind::Snapshot,
+        VersionKind::OldSnapshot,
+        VersionKind::OldBeta,
+        VersionKind::OldAlpha,
+        VersionKind::Experiment,
+        VersionKind::Other("pending".to_owned()),
+    ];
+    let stable: Vec<_> = kinds.iter().map(VersionKind::is_stable).collect();
+    assert_eq!(stable, [true, false, false, false, false, false, false]);
+    let snapshot: Vec<_> = kinds.iter().map(VersionKind::is_snapshot).collect();
+    assert_eq!(snapshot, [false, true, true, false, false, false, false]);
+
+    let ranks: Vec<_> = kinds.iter().map(VersionKind::stability_rank).collect();
+    assert!(ranks.windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn manifest_constructors() {
+    let json = r#"{
+        "latest": {"release": "1.20.4", "snapshot": "1.20.4"},
+        "versions": [
+            {
+                "id": "1.20.4",
+                "type": "release",
+                "url": "https://piston-meta.mojang.com/v1/packages/c98adde5094a3041f486b4d42d0386cf87310559/1.20.4.json",
+                "time": "2024-01-16T12:12:52+00:00",
+                "releaseTime": "2023-12-07T12:56:20+00:00",
+                "sha1": "c98adde5094a3041f486b4d42d0386cf87310559",
+                "complianceLevel": 1
+            }
+        ]
+    }"#;
+    let manifest = Manifest::from_str_json(json).unwrap();
+    assert_eq!(manifest.versions.len(), 1);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        Manifest::from_reader(std::io::Cursor::new(json)).unwrap(),
+        manifest
+    );
+    assert_eq!(Manifest::from_slice(json.as_bytes()).unwrap(), manifest);
+    assert!(Manifest::from_slice(b"[]").is_err());
+}
+
+#[test]
+fn unknown_type_is_other() {
+    let manifest: Manifest = serde_json::from_value(serde_json::json!({
+        "latest": {"release": "1.20.4", "snapshot": "1.20.4"},
+        "versions": [{
+            "id": "1.20.4",
+            "type": "pending",
+            "url": "https://piston-meta.mojang.com/1.20.4.json",
+            "time": "2023-12-07T12:56:20+00:00",
+            "releaseTime": "2023-12-07T12:56:20+00:00",
+        }],
+    }))
+    .unwrap();
+    assert_eq!(
+        manifest.versions[0].kind,
+        VersionKind::Other("pending".to_owned())
+    );
+}
+
+#[test]
+fn manifest_byte_order_mark() {
+    let json = "\u{feff}{\"latest\": {\"release\": \"1.20.4\", \"snapshot\": \"1.20.4\"}, \
+                \"versions\": []}\n";
+    let manifest = Manifest::from_str_json(json).unwrap();
+    assert_eq!(manifest.latest.release, "1.20.4");
+    assert_eq!(Manifest::from_slice(json.as_bytes()).unwrap(), manifest);
+    #[cfg(feature = "std")]
+    assert_eq!(Manifest::from_reader(json.as_bytes()).unwrap(), manifest);
+}
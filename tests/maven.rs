@@ -0,0 +1,59 @@
+use mc_launchermeta::version::maven::{MavenCoord, MavenCoordParseError};
+
+#[test]
+fn coord_round_trip() {
+    let coords = [
+        "com.google.guava:guava:32.1.2-jre",
+        "org.lwjgl:lwjgl:3.3.2:natives-windows-arm64",
+        "de.oceanlabs.mcp:mcp_config:1.20.4-20231207.154220@zip",
+        "net.minecraftforge:forge:1.20.4-49.0.3:installer@jar",
+        "a:b:c",
+    ];
+    for s in coords {
+        let coord: MavenCoord = s.parse().unwrap();
+        assert_eq!(coord.to_string(), s);
+        assert_eq!(coord.to_string().parse::<MavenCoord>().unwrap(), coord);
+    }
+}
+
+#[test]
+fn coord_parts() {
+    let coord: MavenCoord = "org.lwjgl:lwjgl:3.3.2:natives-linux".parse().unwrap();
+    assert_eq!(coord.group, "org.lwjgl");
+    assert_eq!(coord.artifact, "lwjgl");
+    assert_eq!(coord.version, "3.3.2");
+    assert_eq!(coord.classifier.as_deref(), Some("natives-linux"));
+    assert_eq!(coord.extension(), "jar");
+
+    let coord: MavenCoord = "de.oceanlabs.mcp:mcp_config:1.20.4@zip".parse().unwrap();
+    assert_eq!(coord.classifier, None);
+    assert_eq!(coord.extension(), "zip");
+    assert_eq!(
+        coord.path(),
+        "de/oceanlabs/mcp/mcp_config/1.20.4/mcp_config-1.20.4.zip"
+    );
+
+    // the extension as a fifth part is normalized to the `@` form
+    let coord: MavenCoord = "net.minecraftforge:forge:1.20.4:userdev:zip"
+        .parse()
+        .unwrap();
+    assert_eq!(coord.classifier.as_deref(), Some("userdev"));
+    assert_eq!(coord.extension(), "zip");
+    assert_eq!(
+        coord.to_string(),
+        "net.minecraftforge:forge:1.20.4:userdev@zip"
+    );
+}
+
+#[test]
+fn invalid_coords() {
+    let err = |s: &str| s.parse::<MavenCoord>().unwrap_err();
+    assert_eq!(err("a:b"), MavenCoordParseError::MissingPart("version"));
+    assert_eq!(err(""), MavenCoordParseError::EmptyPart("group"));
+    assert_eq!(err("a::c"), MavenCoordParseError::EmptyPart("artifact"));
+    assert_eq!(err("a:b:c:"), MavenCoordParseError::EmptyPart("classifier"));
+    assert_eq!(err("a:b:c@"), MavenCoordParseError::EmptyPart("extension"));
+    assert_eq!(err("a:b:c:d:e:f"), MavenCoordParseError::TooManyParts);
+    assert_eq!(err("a:b:c:d:e@f"), MavenCoordParseError::TooManyParts);
+    assert!(MavenCoord::parse("a:b").is_none());
+}
@@ -0,0 +1,222 @@
+mod common;
+
+use std::collections::HashMap;
+
+use mc_launchermeta::version::library::Library;
+use mc_launchermeta::version::merge::{MergeError, MergePolicy, PartialVersion};
+use mc_launchermeta::version::{Arguments, Version};
+
+fn child(id: &str, parent: &str, library: &str) -> Version {
+    let mut version = common::sample_version();
+    version.id = id.to_owned();
+    version.inherits_from = Some(parent.to_owned());
+    version.libraries = vec![Library::new(library)];
+    version.arguments = Some(Arguments {
+        game: vec![format!("--{}", id).parse().unwrap()],
+        jvm: vec![],
+    });
+    version.java_version = None;
+    version.logging = None;
+    version
+}
+
+#[test]
+fn three_level_chain() {
+    let base = common::sample_version();
+    let loader = child("loader", "1.20.4", "com.example:loader:1.0");
+    let modpack = child("modpack", "loader", "com.example:modpack:1.0");
+    let versions: HashMap<_, _> = [base.clone(), loader]
+        .into_iter()
+        .map(|version| (version.id.clone(), version))
+        .collect();
+
+    let mut loaded = Vec::new();
+    let merged = modpack
+        .resolve_chain(MergePolicy::ChildFirst, |id| {
+            loaded.push(id.to_owned());
+            versions.get(id).cloned()
+        })
+        .unwrap();
+    assert_eq!(loaded, ["loader", "1.20.4"]);
+
+    assert_eq!(merged.id, "modpack");
+    assert_eq!(merged.inherits_from, None);
+    assert_eq!(merged.java_version, base.java_version);
+    assert_eq!(merged.logging, base.logging);
+    assert_eq!(merged.libraries.len(), base.libraries.len() + 2);
+    assert_eq!(merged.libraries[2..], base.libraries[..]);
+    let added: Vec<_> = merged.libraries[..2]
+        .iter()
+        .map(|library| library.name.as_str())
+        .collect();
+    assert_eq!(added, ["com.example:modpack:1.0", "com.example:loader:1.0"]);
+
+    let game = merged.arguments.unwrap().game;
+    let base_game = base.arguments.unwrap().game;
+    assert_eq!(game.len(), base_game.len() + 2);
+    assert_eq!(game[0].values, ["--modpack"]);
+    assert_eq!(game[1].values, ["--loader"]);
+    assert_eq!(game[2..], base_game[..]);
+}
+
+#[test]
+fn merge_policies() {
+    let base = common::sample_version();
+    let loader = child("loader", "1.20.4", "com.example:loader:1.0");
+    let names = |version: &Version| {
+        version
+            .libraries
+            .iter()
+            .map(|library| library.name.clone())
+            .collect::<Vec<_>>()
+    };
+    let base_names = names(&base);
+
+    let merged = loader
+        .clone()
+        .merge_onto(base.clone(), MergePolicy::default());
+    let mut expected = vec!["com.example:loader:1.0".to_owned()];
+    expected.extend(base_names.iter().cloned());
+    assert_eq!(names(&merged), expected);
+    let game = &merged.arguments.as_ref().unwrap().game;
+    assert_eq!(game[0].values, ["--loader"]);
+    assert_eq!(game[1..], base.arguments.as_ref().unwrap().game[..]);
+
+    let merged = loader.merge_onto(base.clone(), MergePolicy::ParentFirst);
+    let mut expected = base_names;
+    expected.push("com.example:loader:1.0".to_owned());
+    assert_eq!(names(&merged), expected);
+    let game = &merged.arguments.as_ref().unwrap().game;
+    assert_eq!(game[game.len() - 1].values, ["--loader"]);
+    assert_eq!(
+        game[..game.len() - 1],
+        base.arguments.as_ref().unwrap().game[..]
+    );
+}
+
+const LOADER_CHILD: &str = r#"{
+    "id": "fabric-loader-0.15.7-1.20.4",
+    "inheritsFrom": "1.20.4",
+    "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+    "arguments": {
+        "game": [],
+        "jvm": ["-DFabricMcEmu= net.minecraft.client.main.Main "]
+    },
+    "libraries": [
+        {
+            "name": "net.fabricmc:fabric-loader:0.15.7",
+            "url": "https://maven.fabricmc.net/"
+        }
+    ]
+}"#;
+
+#[test]
+fn loader_child() {
+    assert!(Version::from_str_json(LOADER_CHILD).is_err());
+    let child: PartialVersion = serde_json::from_str(LOADER_CHILD).unwrap();
+    assert_eq!(child.asset_index, None);
+
+    let base = common::sample_version();
+    let merged = child
+        .resolve_chain(MergePolicy::default(), |id| {
+            assert_eq!(id, "1.20.4");
+            Some(base.clone().into())
+        })
+        .unwrap();
+    assert_eq!(merged.id, "fabric-loader-0.15.7-1.20.4");
+    assert_eq!(merged.inherits_from, None);
+    assert_eq!(
+        merged.main_class,
+        "net.fabricmc.loader.impl.launch.knot.KnotClient"
+    );
+    assert_eq!(merged.asset_index, base.asset_index);
+    assert_eq!(merged.assets, base.assets);
+    assert_eq!(merged.downloads, base.downloads);
+    assert_eq!(
+        merged.minimum_launcher_version,
+        base.minimum_launcher_version
+    );
+    assert_eq!(merged.release_time, base.release_time);
+    assert_eq!(merged.kind, base.kind);
+    assert_eq!(merged.libraries.len(), base.libraries.len() + 1);
+    assert_eq!(
+        merged.libraries[0].name,
+        "net.fabricmc:fabric-loader:0.15.7"
+    );
+
+    let arguments = merged.arguments.unwrap();
+    let base_arguments = base.arguments.clone().unwrap();
+    assert_eq!(arguments.game, base_arguments.game);
+    assert_eq!(arguments.jvm.len(), base_arguments.jvm.len() + 1);
+    assert_eq!(
+        arguments.jvm[0].values,
+        ["-DFabricMcEmu= net.minecraft.client.main.Main "]
+    );
+    assert_eq!(arguments.jvm[1..], base_arguments.jvm[..]);
+
+    // the version the chain ends at has to be complete
+    let err = child
+        .resolve_chain(MergePolicy::default(), |_| {
+            let mut root: PartialVersion = base.clone().into();
+            root.downloads = None;
+            Some(root)
+        })
+        .unwrap_err();
+    assert_eq!(
+        err,
+        MergeError::Incomplete("1.20.4".to_owned(), "downloads")
+    );
+}
+
+#[test]
+fn no_parent() {
+    let version = common::sample_version();
+    let resolved = version
+        .resolve_chain(MergePolicy::default(), |_| panic!("nothing to load"))
+        .unwrap();
+    assert_eq!(resolved, version);
+}
+
+#[test]
+fn cycle() {
+    let a = child("a", "b", "com.example:a:1.0");
+    let b = child("b", "a", "com.example:b:1.0");
+    let err = a
+        .resolve_chain(MergePolicy::default(), |id| {
+            if id == "b" {
+                Some(b.clone())
+            } else {
+                Some(a.clone())
+            }
+        })
+        .unwrap_err();
+    assert_eq!(
+        err,
+        MergeError::Cycle(vec!["a".to_owned(), "b".to_owned(), "a".to_owned()])
+    );
+    assert_eq!(err.to_string(), "inheritance cycle: a -> b -> a");
+}
+
+#[test]
+fn missing_parent() {
+    let version = child("loader", "1.20.4", "com.example:loader:1.0");
+    assert_eq!(
+        version.resolve_chain(MergePolicy::default(), |_| None),
+        Err(MergeError::MissingParent("1.20.4".to_owned()))
+    );
+}
+
+#[test]
+fn too_deep() {
+    let version = child("0", "1", "com.example:example:1.0");
+    let err = version
+        .resolve_chain(MergePolicy::default(), |id| {
+            let next = id.parse::<u32>().unwrap() + 1;
+            Some(child(id, &next.to_string(), "com.example:example:1.0"))
+        })
+        .unwrap_err();
+    match err {
+        MergeError::TooDeep(ids) => assert_eq!(ids.len(), 16),
+        err => panic!("unexpected error {:?}", err),
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "net")]
+
+mod common;
+
+use mc_launchermeta::net::FetchError;
+use mc_launchermeta::sha1::Sha1;
+use mc_launchermeta::version_manifest::Manifest;
+use mc_launchermeta::{VerifyError, VersionKind};
+
+#[tokio::test]
+#[ignore = "requires network access"]
+async fn fetch_latest_release() {
+    let client = reqwest::Client::new();
+    let manifest = Manifest::fetch(&client).await.unwrap();
+    let latest = manifest.get_latest(VersionKind::Release).unwrap();
+    assert!(latest.sha1.is_some());
+
+    let version = latest.fetch_version(&client).await.unwrap();
+    assert_eq!(version.id, latest.id);
+}
+
+#[test]
+fn parse_asset_objects() {
+    const OBJECTS: &[u8] = include_bytes!("data/objects.json");
+
+    let mut index = common::sample_version().asset_index;
+    index.size = OBJECTS.len() as u64;
+    index.sha1 = Sha1::digest(OBJECTS);
+    let objects = index.parse_objects(OBJECTS).unwrap();
+    assert_eq!(objects.objects.len(), 3);
+
+    let with_bom = [b"\xEF\xBB\xBF", OBJECTS].concat();
+    let mut bom_index = index.clone();
+    bom_index.size = with_bom.len() as u64;
+    bom_index.sha1 = Sha1::digest(&with_bom);
+    assert_eq!(bom_index.parse_objects(&with_bom).unwrap(), objects);
+
+    index.size += 1;
+    assert!(matches!(
+        index.parse_objects(OBJECTS),
+        Err(FetchError::Verify(VerifyError::Size { .. }))
+    ));
+    index.size -= 1;
+    index.sha1 = Sha1::digest(b"");
+    assert!(matches!(
+        index.parse_objects(OBJECTS),
+        Err(FetchError::Verify(VerifyError::Hash { .. }))
+    ));
+}
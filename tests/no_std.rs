@@ -0,0 +1,36 @@
+//! Parsing with only the APIs available without the `std` feature
+//!
+//! Run with `cargo test --no-default-features --test no_std` to check the `alloc` only build.
+
+mod common;
+
+use mc_launchermeta::version::rule::{OsName, RuleContext};
+use mc_launchermeta::version::Version;
+use mc_launchermeta::version_manifest::Manifest;
+
+#[test]
+fn sample_parses() {
+    let version = Version::from_str_json(common::SAMPLE_VERSION).unwrap();
+    assert_eq!(version.id, "1.20.4");
+    assert_eq!(
+        Version::from_slice(common::SAMPLE_VERSION.as_bytes()).unwrap(),
+        version
+    );
+
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+    assert!(!version.game_arguments(&ctx).is_empty());
+    assert_eq!(version.natives_libraries(&ctx).len(), 7);
+
+    let legacy = Version::from_str_json(common::LEGACY_VERSION).unwrap();
+    assert!(legacy.uses_legacy_arguments());
+
+    let manifest = Manifest::from_str_json(
+        r#"{"latest": {"release": "1.20.4", "snapshot": "1.20.4"}, "versions": []}"#,
+    )
+    .unwrap();
+    assert!(manifest.versions.is_empty());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use mc_launchermeta::version::Version;
+
+#[test]
+fn sample_version_round_trip() {
+    common::assert_round_trip::<Version>(common::SAMPLE_VERSION);
+}
+
+#[test]
+fn legacy_version_round_trip() {
+    common::assert_round_trip::<Version>(common::LEGACY_VERSION);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn to_json_key_order() {
+    let version = common::sample_version();
+    let expected_order = [
+        "\"arguments\"",
+        "\"assetIndex\"",
+        "\"assets\"",
+        "\"complianceLevel\"",
+        "\"downloads\"",
+        "\"id\"",
+        "\"javaVersion\"",
+        "\"libraries\"",
+        "\"logging\"",
+        "\"mainClass\"",
+        "\"minimumLauncherVersion\"",
+        "\"releaseTime\"",
+        "\"time\"",
+        "\"type\"",
+    ];
+    let pretty = version.to_json_pretty().unwrap();
+    let top_level: Vec<_> = pretty
+        .lines()
+        .filter(|line| line.starts_with("    \"") && !line.starts_with("     "))
+        .map(|line| line.trim_start().split(':').next().unwrap())
+        .collect();
+    assert_eq!(top_level, expected_order);
+    assert_eq!(pretty.trim_end(), common::SAMPLE_VERSION.trim_end());
+    assert_eq!(
+        Version::from_str_json(&version.to_json().unwrap()).unwrap(),
+        version
+    );
+
+    let legacy = common::legacy_version();
+    let pretty = legacy.to_json_pretty().unwrap();
+    assert_eq!(pretty.trim_end(), common::LEGACY_VERSION.trim_end());
+    assert_eq!(
+        Version::from_str_json(&legacy.to_json().unwrap()).unwrap(),
+        legacy
+    );
+}
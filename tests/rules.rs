@@ -0,0 +1,477 @@
+mod common;
+
+use mc_launchermeta::version::rule::{
+    canonicalize_rules,
+    is_allowed,
+    Features,
+    Os,
+    OsName,
+    Rule,
+    RuleAction,
+    RuleContext,
+    RuleContextBuilder,
+};
+
+#[test]
+#[cfg(feature = "std")]
+fn current_context() {
+    let ctx = RuleContext::current().with_feature("is_demo_user", true);
+    let expected = if cfg!(target_os = "windows") {
+        Some(OsName::Windows)
+    } else if cfg!(target_os = "macos") {
+        Some(OsName::Osx)
+    } else if cfg!(target_os = "linux") {
+        Some(OsName::Linux)
+    } else {
+        None
+    };
+    assert_eq!(ctx.os_name, expected);
+    assert_eq!(ctx.arch.as_deref(), Some(std::env::consts::ARCH));
+    assert!(ctx.feature("is_demo_user"));
+    assert!(!ctx.feature("has_custom_resolution"));
+}
+
+#[test]
+fn quick_play_features() {
+    let arguments = common::sample_version().arguments.unwrap();
+    let features: Vec<_> = arguments.game[24..]
+        .iter()
+        .map(|argument| &argument.rules[0].features)
+        .collect();
+    assert_eq!(features[0].has_quick_plays_support, Some(true));
+    assert_eq!(features[1].is_quick_play_singleplayer, Some(true));
+    assert_eq!(features[2].is_quick_play_multiplayer, Some(true));
+    assert_eq!(features[3].is_quick_play_realms, Some(true));
+    assert!(features[3].is_set("is_quick_play_realms"));
+    assert!(!features[3].is_set("is_quick_play_singleplayer"));
+    assert!(features.iter().all(|features| features.other.is_empty()));
+
+    let mut features = Features::default();
+    features.set("has_cool_new_thing", true);
+    assert!(features.is_set("has_cool_new_thing"));
+    assert_eq!(
+        serde_json::to_string(&features).unwrap(),
+        r#"{"has_cool_new_thing":true}"#
+    );
+}
+
+#[test]
+fn canonicalize_permutations() {
+    let rule = |json: &str| serde_json::from_str::<Rule>(json).unwrap();
+    let allow = rule(r#"{"action": "allow"}"#);
+    let allow_osx = rule(r#"{"action": "allow", "os": {"name": "osx"}}"#);
+    let allow_windows = rule(r#"{"action": "allow", "os": {"name": "windows"}}"#);
+    let demo = rule(r#"{"action": "allow", "features": {"is_demo_user": true}}"#);
+    let disallow_linux = rule(r#"{"action": "disallow", "os": {"name": "linux"}}"#);
+
+    let mut a = vec![
+        allow_windows.clone(),
+        demo.clone(),
+        allow_osx.clone(),
+        disallow_linux.clone(),
+    ];
+    let mut b = vec![
+        allow_osx.clone(),
+        allow_windows.clone(),
+        demo.clone(),
+        allow_osx,
+        disallow_linux.clone(),
+    ];
+    canonicalize_rules(&mut a);
+    canonicalize_rules(&mut b);
+    assert_eq!(a, b);
+    assert_eq!(a.len(), 4);
+    assert_eq!(a[3], disallow_linux);
+
+    // rules with different actions keep their order
+    let mut c = vec![disallow_linux.clone(), allow.clone()];
+    canonicalize_rules(&mut c);
+    assert_eq!(c, [disallow_linux, allow]);
+}
+
+#[test]
+fn native_classifiers() {
+    let ctx = |os_name, arch: &str| {
+        RuleContext {
+            os_name: Some(os_name),
+            arch: Some(arch.to_owned()),
+            ..RuleContext::default()
+        }
+    };
+    assert_eq!(
+        ctx(OsName::Osx, "aarch64").native_classifier(),
+        "natives-macos-arm64"
+    );
+    assert_eq!(
+        ctx(OsName::Osx, "x86_64").native_classifier(),
+        "natives-macos"
+    );
+    assert_eq!(
+        ctx(OsName::Windows, "x86").native_classifier(),
+        "natives-windows-x86"
+    );
+    assert_eq!(
+        ctx(OsName::Windows, "aarch64").native_classifier(),
+        "natives-windows-arm64"
+    );
+    assert_eq!(
+        ctx(OsName::Windows, "x86_64").native_classifier(),
+        "natives-windows"
+    );
+    assert_eq!(
+        ctx(OsName::Linux, "x86_64").native_classifier(),
+        "natives-linux"
+    );
+}
+
+#[test]
+fn rule_actions() {
+    for (json, action) in [
+        ("\"allow\"", RuleAction::Allow),
+        ("\"disallow\"", RuleAction::Disallow),
+    ] {
+        assert_eq!(serde_json::from_str::<RuleAction>(json).unwrap(), action);
+        assert_eq!(serde_json::to_string(&action).unwrap(), json);
+    }
+
+    let err = serde_json::from_str::<Rule>(r#"{"action": "alow"}"#).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown variant `alow`"),
+        "{}",
+        err
+    );
+    assert!(serde_json::from_str::<RuleAction>("\"Allow\"").is_err());
+}
+
+#[test]
+fn os_and_features_combined() {
+    let rule: Rule = serde_json::from_str(
+        r#"{
+            "action": "allow",
+            "os": {"name": "windows", "arch": "x86"},
+            "features": {"has_custom_resolution": true, "is_demo_user": false}
+        }"#,
+    )
+    .unwrap();
+    let windows = RuleContext {
+        os_name: Some(OsName::Windows),
+        arch: Some("x86".to_owned()),
+        ..RuleContext::default()
+    }
+    .with_feature("has_custom_resolution", true);
+
+    assert!(rule.matches(&windows));
+    assert!(is_allowed(std::slice::from_ref(&rule), &windows));
+
+    let linux = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..windows.clone()
+    };
+    assert!(!rule.matches(&linux));
+
+    let x86_64 = RuleContext {
+        arch: Some("x86_64".to_owned()),
+        ..windows.clone()
+    };
+    assert!(!rule.matches(&x86_64));
+
+    let no_resolution = windows.clone().with_feature("has_custom_resolution", false);
+    assert!(!rule.matches(&no_resolution));
+
+    let demo = windows.with_feature("is_demo_user", true);
+    assert!(!rule.matches(&demo));
+}
+
+#[test]
+fn os_names() {
+    for (json, name) in [
+        ("\"windows\"", OsName::Windows),
+        ("\"osx\"", OsName::Osx),
+        ("\"linux\"", OsName::Linux),
+        ("\"freebsd\"", OsName::Other("freebsd".to_owned())),
+    ] {
+        assert_eq!(serde_json::from_str::<OsName>(json).unwrap(), name);
+        assert_eq!(serde_json::to_string(&name).unwrap(), json);
+    }
+
+    let rule: Rule =
+        serde_json::from_str(r#"{"action": "allow", "os": {"name": "freebsd"}}"#).unwrap();
+    let freebsd = RuleContext {
+        os_name: Some(OsName::Other("freebsd".to_owned())),
+        ..RuleContext::default()
+    };
+    assert!(is_allowed(std::slice::from_ref(&rule), &freebsd));
+    assert!(!is_allowed(&[rule], &RuleContext::default()));
+    assert_eq!(freebsd.native_classifier(), "natives-linux");
+}
+
+#[test]
+fn context_builder_registers_vars() {
+    let (ctx, vars) = RuleContextBuilder::new(RuleContext::default())
+        .custom_resolution(1920, 1080)
+        .var("auth_player_name", "Steve")
+        .build();
+    assert!(ctx.feature("has_custom_resolution"));
+    assert!(!ctx.feature("is_demo_user"));
+    assert_eq!(vars["resolution_width"], "1920");
+    assert_eq!(vars["resolution_height"], "1080");
+    assert_eq!(vars["auth_player_name"], "Steve");
+
+    // every placeholder the enabled feature brings in has a value
+    let version = common::sample_version();
+    let without = version.required_placeholders(&RuleContext::default());
+    let with = version.required_placeholders(&ctx);
+    let added: Vec<_> = with.difference(&without).collect();
+    assert!(!added.is_empty());
+    assert!(added.iter().all(|name| vars.contains_key(name.as_str())));
+
+    let (ctx, vars) = RuleContextBuilder::from(RuleContext::default())
+        .quick_play_path("quickplay.json")
+        .quick_play_multiplayer("mc.example.com")
+        .build();
+    assert!(ctx.feature("has_quick_plays_support"));
+    assert!(ctx.feature("is_quick_play_multiplayer"));
+    assert_eq!(vars.len(), 2);
+    assert_eq!(vars["quickPlayMultiplayer"], "mc.example.com");
+}
+
+/// The game arguments of the sample version without any features, and with `ctx`
+fn preset_arguments(ctx: &RuleContext) -> (Vec<String>, Vec<String>) {
+    let version = common::sample_version();
+    let base = version.game_arguments(&RuleContext::default());
+    let args = version.game_arguments(ctx);
+    assert_eq!(&args[..base.len()], base.as_slice());
+    (base, args)
+}
+
+#[test]
+fn demo_preset() {
+    let (ctx, vars) = RuleContext::demo().build();
+    assert!(ctx.feature("is_demo_user"));
+    assert!(vars.is_empty());
+    let (base, args) = preset_arguments(&ctx);
+    assert!(!base.iter().any(|arg| arg == "--demo"));
+    assert_eq!(&args[base.len()..], ["--demo"]);
+}
+
+#[test]
+fn resolution_preset() {
+    let (ctx, vars) = RuleContext::with_resolution(1280, 720).build();
+    assert!(ctx.feature("has_custom_resolution"));
+    assert_eq!(vars["resolution_width"], "1280");
+    assert_eq!(vars["resolution_height"], "720");
+    let (base, args) = preset_arguments(&ctx);
+    assert_eq!(
+        &args[base.len()..],
+        [
+            "--width",
+            "${resolution_width}",
+            "--height",
+            "${resolution_height}"
+        ]
+    );
+}
+
+#[test]
+fn quick_play_singleplayer_preset() {
+    let (ctx, vars) = RuleContext::quick_play_singleplayer("New World").build();
+    assert!(ctx.feature("is_quick_play_singleplayer"));
+    assert_eq!(vars["quickPlaySingleplayer"], "New World");
+    let (base, args) = preset_arguments(&ctx);
+    assert_eq!(
+        &args[base.len()..],
+        ["--quickPlaySingleplayer", "${quickPlaySingleplayer}"]
+    );
+
+    // presets are builders, so they combine with the other setters
+    let (ctx, _) = RuleContext::quick_play_singleplayer("New World")
+        .demo_user()
+        .build();
+    assert!(ctx.feature("is_demo_user"));
+    let (base, args) = preset_arguments(&ctx);
+    assert_eq!(
+        &args[base.len()..],
+        [
+            "--demo",
+            "--quickPlaySingleplayer",
+            "${quickPlaySingleplayer}"
+        ]
+    );
+}
+
+#[test]
+fn lenient_feature_values() {
+    let features: Features = serde_json::from_value(serde_json::json!({
+        "is_demo_user": 1,
+        "has_custom_resolution": "false",
+        "is_quick_play_realms": 0,
+        "has_quick_plays_support": true,
+        "custom_feature": "true",
+        "other_feature": -1,
+    }))
+    .unwrap();
+    assert_eq!(features.is_demo_user, Some(true));
+    assert_eq!(features.has_custom_resolution, Some(false));
+    assert_eq!(features.is_quick_play_realms, Some(false));
+    assert_eq!(features.has_quick_plays_support, Some(true));
+    assert_eq!(features.get("custom_feature"), Some(true));
+    assert_eq!(features.get("other_feature"), Some(true));
+
+    assert_eq!(
+        serde_json::to_value(&features).unwrap(),
+        serde_json::json!({
+            "is_demo_user": true,
+            "has_custom_resolution": false,
+            "is_quick_play_realms": false,
+            "has_quick_plays_support": true,
+            "custom_feature": true,
+            "other_feature": true,
+        })
+    );
+
+    let rule: Rule = serde_json::from_str(
+        r#"{"action": "allow", "features": {"has_custom_resolution": "true"}}"#,
+    )
+    .unwrap();
+    let ctx = RuleContext::default().with_feature("has_custom_resolution", true);
+    assert!(rule.matches(&ctx));
+
+    assert!(serde_json::from_str::<Features>(r#"{"is_demo_user": "yes"}"#).is_err());
+    assert!(serde_json::from_str::<Features>(r#"{"custom_feature": "yes"}"#).is_err());
+}
+
+#[test]
+fn os_condition() {
+    let version = common::sample_version();
+    let jvm = &version.arguments.as_ref().unwrap().jvm;
+    let first_thread = jvm
+        .iter()
+        .find(|argument| argument.values == ["-XstartOnFirstThread"])
+        .unwrap();
+    let condition: &Os = first_thread.rules[0].os_condition().unwrap();
+    assert_eq!(condition.name(), Some(&OsName::Osx));
+    assert_eq!(condition.arch(), None);
+    assert_eq!(condition.version(), None);
+
+    let stack_size = jvm
+        .iter()
+        .find(|argument| argument.values == ["-Xss1M"])
+        .unwrap();
+    let condition = stack_size.rules[0].os_condition().unwrap();
+    assert_eq!(condition.name(), None);
+    assert_eq!(condition.arch(), Some("x86"));
+
+    let rule: Rule = serde_json::from_str(
+        r#"{"action": "disallow", "os": {"name": "windows", "version": "^10\\."}}"#,
+    )
+    .unwrap();
+    assert_eq!(rule.os_condition().unwrap().version(), Some("^10\\."));
+    let unconditional: Rule = serde_json::from_str(r#"{"action": "allow"}"#).unwrap();
+    assert!(unconditional.os_condition().is_none());
+
+    // architectures other than x86 are kept as they are
+    let json = r#"{"action":"allow","os":{"arch":"arm64"}}"#;
+    let rule: Rule = serde_json::from_str(json).unwrap();
+    assert_eq!(rule.os_condition().unwrap().arch(), Some("arm64"));
+    assert_eq!(serde_json::to_string(&rule).unwrap(), json);
+    let arm64 = RuleContext {
+        arch: Some("arm64".to_owned()),
+        ..RuleContext::default()
+    };
+    assert!(rule.os_condition().unwrap().matches(&arm64));
+    assert!(!rule
+        .os_condition()
+        .unwrap()
+        .matches(&RuleContext::default()));
+}
+
+#[test]
+fn referenced_features() {
+    let version = common::sample_version();
+    let features = version.all_referenced_features();
+    assert_eq!(
+        features.iter().map(String::as_str).collect::<Vec<_>>(),
+        [
+            "has_custom_resolution",
+            "has_quick_plays_support",
+            "is_demo_user",
+            "is_quick_play_multiplayer",
+            "is_quick_play_realms",
+            "is_quick_play_singleplayer",
+        ]
+    );
+    assert!(common::legacy_version()
+        .all_referenced_features()
+        .is_empty());
+
+    let game = &version.arguments.as_ref().unwrap().game;
+    let rule = game
+        .iter()
+        .find(|argument| argument.values == ["--demo"])
+        .map(|argument| &argument.rules[0])
+        .unwrap();
+    let required: Vec<_> = rule.required_features().iter().collect();
+    assert_eq!(required, [("is_demo_user", true)]);
+    assert!(version
+        .arguments
+        .as_ref()
+        .unwrap()
+        .jvm
+        .iter()
+        .all(|argument| {
+            argument
+                .rules
+                .iter()
+                .all(|rule| rule.required_features().is_empty())
+        }));
+}
+
+#[test]
+fn os_version_patterns() {
+    let windows = |version: &str| {
+        RuleContext {
+            os_name: Some(OsName::Windows),
+            os_version: Some(version.to_owned()),
+            ..RuleContext::default()
+        }
+    };
+    let osx = |version: &str| {
+        RuleContext {
+            os_name: Some(OsName::Osx),
+            os_version: Some(version.to_owned()),
+            ..RuleContext::default()
+        }
+    };
+
+    // from the natives of older versions, which are disallowed on OS X 10.5
+    let rule: Rule = serde_json::from_str(
+        r#"{"action": "disallow", "os": {"name": "osx", "version": "^10\\.5\\.\\d$"}}"#,
+    )
+    .unwrap();
+    assert!(rule.matches(&osx("10.5.8")));
+    assert!(rule.matches(&osx("10.5.0")));
+    assert!(!rule.matches(&osx("10.5.10")));
+    assert!(!rule.matches(&osx("10.5")));
+    assert!(!rule.matches(&osx("10.15.7")));
+    assert!(!rule.matches(&osx("110.5.8")));
+    assert!(!rule.matches(&osx("10x5x8")));
+
+    let rule: Rule = serde_json::from_str(
+        r#"{"action": "allow", "os": {"name": "windows", "version": "^10\\."}}"#,
+    )
+    .unwrap();
+    assert!(rule.matches(&windows("10.0")));
+    assert!(!rule.matches(&windows("100.0")));
+    assert!(!rule.matches(&windows("6.1")));
+
+    // unanchored patterns match anywhere, `.` matches any character
+    let rule: Rule =
+        serde_json::from_str(r#"{"action": "allow", "os": {"version": "0.1"}}"#).unwrap();
+    assert!(rule.matches(&windows("10.19041")));
+    assert!(rule.matches(&windows("10x1")));
+
+    // unsupported syntax never matches rather than matching the wrong versions
+    let rule: Rule =
+        serde_json::from_str(r#"{"action": "allow", "os": {"version": "^10\\..*$"}}"#).unwrap();
+    assert!(!rule.matches(&windows("10.0")));
+}
@@ -0,0 +1,37 @@
+use mc_launchermeta::sha1::{Sha1, Sha1ParseError};
+use mc_launchermeta::version::Download;
+
+const HASH: &str = "fd19469fed4a4b4c15b2d5133985f0e3e7816a8a";
+
+#[test]
+fn sha1_round_trip() {
+    let sha1: Sha1 = HASH.parse().unwrap();
+    assert_eq!(sha1.to_string(), HASH);
+    assert_eq!(sha1.as_bytes()[0], 0xFD);
+
+    let json = format!(
+        r#"{{"sha1":"{}","size":22876,"url":"https://example.com/client.jar"}}"#,
+        HASH
+    );
+    let download: Download = serde_json::from_str(&json).unwrap();
+    assert_eq!(download.sha1, sha1);
+    assert_eq!(serde_json::to_string(&download).unwrap(), json);
+}
+
+#[test]
+fn sha1_rejects_invalid() {
+    assert_eq!(
+        HASH[..39].parse::<Sha1>(),
+        Err(Sha1ParseError::InvalidLength(39))
+    );
+    assert_eq!(
+        HASH.to_uppercase().parse::<Sha1>(),
+        Err(Sha1ParseError::InvalidCharacter {
+            index: 0,
+            found: 'F'
+        })
+    );
+
+    let json = r#"{"sha1":"not a hash","size":1,"url":"https://example.com/a.jar"}"#;
+    assert!(serde_json::from_str::<Download>(json).is_err());
+}
@@ -0,0 +1,791 @@
+mod common;
+
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use mc_launchermeta::download::Downloadable;
+use mc_launchermeta::sha1::Sha1;
+use mc_launchermeta::version::borrowed::VersionRef;
+use mc_launchermeta::version::rule::{OsName, RuleContext};
+use mc_launchermeta::version::validate::ValidationError;
+use mc_launchermeta::version::{
+    ArgumentDiff,
+    Download,
+    JavaVersion,
+    ManifestFormat,
+    Side,
+    Version,
+    SUPPORTED_LAUNCHER_VERSION,
+};
+
+#[test]
+fn downloads_iter_labels() {
+    let version = common::sample_version();
+    let labels: Vec<_> = version.downloads.iter().map(|(label, _)| label).collect();
+    assert_eq!(
+        labels,
+        ["client", "client_mappings", "server", "server_mappings"]
+    );
+}
+
+#[test]
+fn download_file_name() {
+    let version = common::sample_version();
+    assert_eq!(version.downloads.client.file_name(), Some("client.jar"));
+
+    let mut download = version.downloads.client;
+    download.url = "https://piston-data.mojang.com/v1/objects/".to_owned();
+    assert_eq!(download.file_name(), None);
+    download.url = "https://piston-data.mojang.com/client.jar?token=abc".to_owned();
+    assert_eq!(download.file_name(), None);
+    download.url = "https://piston-data.mojang.com".to_owned();
+    assert_eq!(download.file_name(), None);
+}
+
+#[test]
+fn legacy_arguments() {
+    let version = common::legacy_version();
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..RuleContext::default()
+    };
+
+    let game = version.game_arguments(&ctx);
+    assert_eq!(game[..2], ["--username", "${auth_player_name}"]);
+    assert_eq!(game.len(), 18);
+
+    let jvm = version.jvm_arguments(&ctx);
+    assert_eq!(jvm[0], "-Djava.library.path=${natives_directory}");
+    assert!(!jvm.contains(&"-XstartOnFirstThread".to_owned()));
+    assert_eq!(jvm[jvm.len() - 2..], ["-cp", "${classpath}"]);
+}
+
+#[test]
+fn modern_arguments() {
+    let version = common::sample_version();
+    let ctx = RuleContext {
+        os_name: Some(OsName::Osx),
+        ..RuleContext::default()
+    };
+
+    let game = version.game_arguments(&ctx);
+    assert_eq!(game.len(), 22);
+    assert!(!game.contains(&"--demo".to_owned()));
+
+    let jvm = version.jvm_arguments(&ctx);
+    assert_eq!(jvm[0], "-XstartOnFirstThread");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn classpath() {
+    let version = common::sample_version();
+    let ctx = RuleContext {
+        os_name: Some(OsName::Osx),
+        ..RuleContext::default()
+    };
+    let libraries = Path::new("libraries");
+    let classpath = version.classpath(&ctx, libraries, Path::new("client.jar"));
+
+    let glfw = libraries.join("org/lwjgl/lwjgl-glfw/3.3.2/lwjgl-glfw-3.3.2.jar");
+    let glfw_natives =
+        libraries.join("org/lwjgl/lwjgl-glfw/3.3.2/lwjgl-glfw-3.3.2-natives-macos.jar");
+    assert!(classpath.contains(&glfw));
+    assert!(!classpath.contains(&glfw_natives));
+    assert!(classpath
+        .contains(&libraries.join("ca/weblite/java-objc-bridge/1.1/java-objc-bridge-1.1.jar")));
+    assert!(!classpath
+        .iter()
+        .any(|path| path.to_string_lossy().contains("epoll-4.1.97.Final-linux")));
+    assert_eq!(classpath.last().unwrap(), Path::new("client.jar"));
+    assert_eq!(classpath.len(), 45);
+
+    // paths that could escape the libraries directory are left out
+    let mut version = version;
+    for (name, path) in [
+        ("com.example:parent:1.0", "../../parent-1.0.jar"),
+        ("com.example:absolute:1.0", "/tmp/absolute-1.0.jar"),
+    ] {
+        let mut library = version.libraries[0].clone();
+        library.name = name.to_owned();
+        library.rules = None;
+        if let Some(artifact) = library.downloads.as_mut().and_then(|d| d.artifact.as_mut()) {
+            artifact.path = path.to_owned();
+        }
+        version.libraries.push(library);
+    }
+    let unsafe_classpath = version.classpath(&ctx, libraries, Path::new("client.jar"));
+    assert_eq!(unsafe_classpath, classpath);
+}
+
+#[test]
+fn required_java() {
+    let legacy = common::legacy_version();
+    assert!(legacy.java_version.is_none());
+    assert_eq!(legacy.required_java(), JavaVersion::jre_legacy());
+    assert_eq!(legacy.required_java().major_version, 8);
+
+    let modern = common::sample_version();
+    assert_eq!(modern.required_java(), JavaVersion::java_runtime_gamma());
+}
+
+#[test]
+fn java_major_version() {
+    let parse = |major_version: serde_json::Value| {
+        serde_json::from_value::<JavaVersion>(serde_json::json!({
+            "component": "java-runtime-delta",
+            "majorVersion": major_version,
+        }))
+    };
+    assert_eq!(parse(serde_json::json!(17)).unwrap().major_version, 17);
+    assert_eq!(parse(serde_json::json!("21")).unwrap().major_version, 21);
+    let future = parse(serde_json::json!(300)).unwrap();
+    assert_eq!(future.major_version, 300);
+    assert!(parse(serde_json::json!(70000)).is_err());
+    assert!(parse(serde_json::json!("twenty")).is_err());
+
+    // always written as a number
+    let json = serde_json::to_value(parse(serde_json::json!("21")).unwrap()).unwrap();
+    assert_eq!(json["majorVersion"], serde_json::json!(21));
+}
+
+#[test]
+fn java_download_selector() {
+    let ctx = |os_name, arch: &str| {
+        RuleContext {
+            os_name: Some(os_name),
+            arch: Some(arch.to_owned()),
+            ..RuleContext::default()
+        }
+    };
+    let linux = ctx(OsName::Linux, "x86_64");
+    let mac_arm = ctx(OsName::Osx, "aarch64");
+    assert_eq!(linux.java_platform_key(), "linux");
+    assert_eq!(mac_arm.java_platform_key(), "mac-os-arm64");
+    assert_eq!(ctx(OsName::Osx, "x86_64").java_platform_key(), "mac-os");
+    assert_eq!(
+        ctx(OsName::Windows, "x86_64").java_platform_key(),
+        "windows-x64"
+    );
+    assert_eq!(
+        ctx(OsName::Windows, "x86").java_platform_key(),
+        "windows-x86"
+    );
+    assert_eq!(
+        ctx(OsName::Windows, "aarch64").java_platform_key(),
+        "windows-arm64"
+    );
+    assert_eq!(ctx(OsName::Linux, "x86").java_platform_key(), "linux-i386");
+    assert_eq!(RuleContext::default().java_platform_key(), "linux");
+
+    let version = common::sample_version();
+    assert_eq!(version.java_component_str(), "java-runtime-gamma");
+    assert_eq!(
+        version.java_download_selector(&linux),
+        ("java-runtime-gamma".to_owned(), "linux")
+    );
+    assert_eq!(
+        version.java_download_selector(&mac_arm),
+        ("java-runtime-gamma".to_owned(), "mac-os-arm64")
+    );
+
+    let legacy = common::legacy_version();
+    assert_eq!(
+        legacy.java_component_str(),
+        legacy.required_java().component
+    );
+    assert_eq!(
+        legacy.java_download_selector(&mac_arm),
+        ("jre-legacy".to_owned(), "mac-os-arm64")
+    );
+}
+
+#[test]
+fn validate() {
+    assert_eq!(common::sample_version().validate(), Ok(()));
+    assert_eq!(common::legacy_version().validate(), Ok(()));
+
+    let mut version = common::sample_version();
+    version.main_class = String::new();
+    version.minecraft_arguments = Some("--username ${auth_player_name} --foo ${bar}".to_owned());
+    assert_eq!(
+        version.validate(),
+        Err(vec![
+            ValidationError::EmptyMainClass,
+            ValidationError::UnknownPlaceholder {
+                argument: "${bar}".to_owned(),
+                placeholder: "bar".to_owned(),
+            },
+        ])
+    );
+}
+
+#[test]
+fn client_only_downloads_omit_missing() {
+    let mut downloads = common::sample_version().downloads;
+    downloads.client_mappings = None;
+    downloads.server = None;
+    downloads.server_mappings = None;
+
+    let json = serde_json::to_value(&downloads).unwrap();
+    let keys: Vec<_> = json.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["client"]);
+}
+
+#[test]
+fn server_libraries() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..RuleContext::default()
+    };
+    for version in [common::sample_version(), common::legacy_version()] {
+        let libraries = version.server_libraries(&ctx);
+        assert!(libraries
+            .iter()
+            .all(|library| !library.name.starts_with("org.lwjgl")));
+        assert!(libraries
+            .iter()
+            .any(|library| library.name.starts_with("com.google.guava:guava:")));
+    }
+
+    let version = common::sample_version();
+    let natives: Vec<_> = version
+        .libraries
+        .iter()
+        .filter(|library| library.is_native())
+        .collect();
+    assert!(natives
+        .iter()
+        .any(|library| library.name == "org.lwjgl:lwjgl:3.3.2:natives-linux"));
+    assert!(natives
+        .iter()
+        .all(|library| library.name.contains(":natives-")));
+    assert!(common::legacy_version()
+        .libraries
+        .iter()
+        .any(|library| library.is_native() && library.name.contains("lwjgl-platform")));
+}
+
+#[test]
+fn required_placeholders() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        ..RuleContext::default()
+    };
+    let placeholders = common::sample_version().required_placeholders(&ctx);
+    for name in [
+        "auth_player_name",
+        "classpath",
+        "natives_directory",
+        "launcher_name",
+        "path",
+    ] {
+        assert!(placeholders.contains(name), "missing {}", name);
+    }
+    // the resolution arguments only apply with the feature enabled
+    assert!(!placeholders.contains("resolution_width"));
+
+    let ctx = ctx.with_feature("has_custom_resolution", true);
+    let placeholders = common::sample_version().required_placeholders(&ctx);
+    assert!(placeholders.contains("resolution_width"));
+
+    let placeholders = common::legacy_version().required_placeholders(&ctx);
+    assert!(placeholders.contains("user_properties"));
+    assert!(placeholders.contains("classpath"));
+}
+
+#[test]
+fn version_constructors() {
+    let expected = common::sample_version();
+    let bytes = common::SAMPLE_VERSION.as_bytes();
+    assert_eq!(Version::from_slice(bytes).unwrap(), expected);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        Version::from_reader(std::io::Cursor::new(bytes)).unwrap(),
+        expected
+    );
+    assert_eq!(
+        Version::from_str_json(common::SAMPLE_VERSION).unwrap(),
+        expected
+    );
+
+    let err = Version::from_slice(b"{}").unwrap_err();
+    assert!(matches!(err, mc_launchermeta::Error::Parse(_)));
+}
+
+#[test]
+fn format_version() {
+    let modern = common::sample_version();
+    assert!(!modern.uses_legacy_arguments());
+    assert_eq!(modern.format_version(), ManifestFormat::ComplianceLevel);
+
+    let mut version = modern.clone();
+    version.compliance_level = None;
+    assert_eq!(version.format_version(), ManifestFormat::JavaVersion);
+    version.java_version = None;
+    assert_eq!(version.format_version(), ManifestFormat::Arguments);
+
+    let mut legacy = common::legacy_version();
+    assert!(legacy.uses_legacy_arguments());
+    assert_eq!(legacy.format_version(), ManifestFormat::PreLogging);
+    legacy.logging = modern.logging;
+    assert_eq!(legacy.format_version(), ManifestFormat::Legacy);
+    assert!(legacy.format_version() < ManifestFormat::Arguments);
+}
+
+#[test]
+fn argument_parity() {
+    assert_eq!(common::sample_version().check_argument_parity(), Ok(()));
+    assert_eq!(common::legacy_version().check_argument_parity(), Ok(()));
+
+    let mut version = common::sample_version();
+    let ctx = RuleContext::default();
+    let game = version.arguments.as_ref().unwrap().build_game(&ctx);
+    version.minecraft_arguments = Some(game.join(" "));
+    assert_eq!(version.check_argument_parity(), Ok(()));
+
+    version.minecraft_arguments = Some(game[2..].join(" ") + " --tweakClass foo");
+    assert_eq!(
+        version.check_argument_parity(),
+        Err(ArgumentDiff {
+            added: vec!["--username".to_owned(), "${auth_player_name}".to_owned()],
+            removed: vec!["--tweakClass".to_owned(), "foo".to_owned()],
+        })
+    );
+}
+
+#[test]
+fn total_download_size() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+    let version = common::sample_version();
+    // 52 libraries for linux, including the natives-linux jars, plus the client jar, asset index
+    // and logging config
+    assert_eq!(version.total_download_size(&ctx), 34391853);
+
+    let objects = serde_json::from_str(include_str!("data/objects.json")).unwrap();
+    assert_eq!(
+        version.total_download_size_with_assets(&ctx, &objects),
+        34391853 + 3665 + 440 + 27814
+    );
+}
+
+#[test]
+fn extra_download_sides() {
+    let mut json: serde_json::Value = serde_json::from_str(common::SAMPLE_VERSION).unwrap();
+    json["downloads"]["arm_server"] = serde_json::json!({
+        "sha1": "8dd1a28015f51b1803213892b50b7b4fc76e594d",
+        "size": 49150256,
+        "url": "https://piston-data.mojang.com/v1/objects/8dd1a28015f51b1803213892b50b7b4fc76e594d/arm_server.jar"
+    });
+    let version: Version = serde_json::from_value(json.clone()).unwrap();
+
+    let extra: Vec<_> = version.downloads.extra_sides().collect();
+    assert_eq!(extra.len(), 1);
+    assert_eq!(extra[0].0, "arm_server");
+    assert_eq!(extra[0].1.size, 49150256);
+    assert_eq!(extra[0].1.file_name(), Some("arm_server.jar"));
+    // the known sides are unaffected
+    assert_eq!(version.downloads.iter().count(), 4);
+    assert_eq!(serde_json::to_value(&version).unwrap(), json);
+
+    assert_eq!(common::sample_version().downloads.extra_sides().count(), 0);
+}
+
+#[test]
+fn mappings_downloads() {
+    let downloads = common::sample_version().downloads;
+    let mappings: Vec<_> = downloads.mappings().collect();
+    assert_eq!(
+        mappings,
+        [
+            (Side::Client, downloads.client_mappings.as_ref().unwrap()),
+            (Side::Server, downloads.server_mappings.as_ref().unwrap()),
+        ]
+    );
+    assert_eq!(mappings[0].1.file_name(), Some("client.txt"));
+
+    assert_eq!(common::legacy_version().downloads.mappings().count(), 0);
+}
+
+#[test]
+fn rewrite_urls() {
+    let hosts = [
+        "libraries.minecraft.net",
+        "piston-data.mojang.com",
+        "piston-meta.mojang.com",
+        "launcher.mojang.com",
+        "launchermeta.mojang.com",
+    ];
+    let mut with_server_logging = common::sample_version();
+    if let Some(logging) = &mut with_server_logging.logging {
+        logging.server = Some(logging.client.clone());
+    }
+    for mut version in [
+        common::sample_version(),
+        common::legacy_version(),
+        with_server_logging,
+    ] {
+        let original = version.clone();
+        version.rewrite_urls(|url| {
+            let (host, path) = url.strip_prefix("https://")?.split_once('/')?;
+            Some(format!("https://mirror.example.com/{}/{}", host, path))
+        });
+
+        let json = serde_json::to_string(&version).unwrap();
+        for host in hosts {
+            assert!(!json.contains(&format!("https://{}", host)), "{}", host);
+        }
+        assert_eq!(
+            version.downloads.client.url,
+            original
+                .downloads
+                .client
+                .url
+                .replace("https://", "https://mirror.example.com/")
+        );
+        if let Some(server) = version.logging.and_then(|logging| logging.server) {
+            assert!(server
+                .file
+                .url
+                .starts_with("https://mirror.example.com/piston-data.mojang.com/"));
+        }
+    }
+
+    let mut version = common::sample_version();
+    version.rewrite_urls(|_| None);
+    assert_eq!(version, common::sample_version());
+}
+
+#[test]
+fn artifacts() {
+    let ctx = RuleContext {
+        os_name: Some(OsName::Linux),
+        arch: Some("x86_64".to_owned()),
+        ..RuleContext::default()
+    };
+    let version = common::sample_version();
+    let artifacts: Vec<_> = version.artifacts(&ctx).collect();
+    assert_eq!(artifacts.len(), 52);
+    assert!(artifacts
+        .iter()
+        .any(|artifact| artifact.path.ends_with("lwjgl-3.3.2-natives-linux.jar")));
+    assert!(!artifacts
+        .iter()
+        .any(|artifact| artifact.path.contains("natives-windows")));
+
+    // old natives model, where the natives come from the classifiers
+    let version = common::legacy_version();
+    let artifacts: Vec<_> = version.artifacts(&ctx).collect();
+    assert!(artifacts
+        .iter()
+        .any(|artifact| artifact.path.ends_with("natives-linux.jar")));
+    assert!(!artifacts
+        .iter()
+        .any(|artifact| artifact.path.contains("natives-osx")));
+}
+
+#[test]
+fn compliance() {
+    let mut version = common::sample_version();
+    assert_eq!(version.compliance_level, Some(1));
+    assert!(version.is_compliant());
+    version.compliance_level = Some(0);
+    assert!(!version.is_compliant());
+    version.compliance_level = None;
+    assert!(!version.is_compliant());
+    assert!(!common::legacy_version().is_compliant());
+}
+
+#[test]
+fn string_sizes() {
+    let json = |size: &str| {
+        format!(
+            r#"{{
+                "sha1": "884f4fd92fdb1f2dc6a0811f23368f032a7f166b",
+                "size": {},
+                "url": "https://piston-data.mojang.com/v1/objects/884f4fd92fdb1f2dc6a0811f23368f032a7f166b/client.jar"
+            }}"#,
+            size
+        )
+    };
+    let numeric: Download = serde_json::from_str(&json("426900")).unwrap();
+    let string: Download = serde_json::from_str(&json("\"426900\"")).unwrap();
+    assert_eq!(numeric.size, 426900);
+    assert_eq!(string, numeric);
+    assert_eq!(serde_json::to_value(&string).unwrap()["size"], 426900);
+
+    assert!(serde_json::from_str::<Download>(&json("\"big\"")).is_err());
+    assert!(serde_json::from_str::<Download>(&json("-1")).is_err());
+
+    let mut version: serde_json::Value = serde_json::from_str(common::SAMPLE_VERSION).unwrap();
+    version["assetIndex"]["totalSize"] = "626413221".into();
+    version["libraries"][0]["downloads"]["artifact"]["size"] = "1234".into();
+    let version: Version = serde_json::from_value(version).unwrap();
+    assert_eq!(version.asset_index.total_size, 626413221);
+    let artifact = version.libraries[0]
+        .downloads
+        .as_ref()
+        .unwrap()
+        .artifact
+        .as_ref();
+    assert_eq!(artifact.unwrap().size, 1234);
+}
+
+#[test]
+fn jar_downloads() {
+    let version = common::sample_version();
+    assert_eq!(
+        version.client_download().sha1.to_string(),
+        "884f4fd92fdb1f2dc6a0811f23368f032a7f166b"
+    );
+    assert_eq!(
+        version.download_for(Side::Client),
+        Some(version.client_download())
+    );
+    assert_eq!(
+        version
+            .download_for(Side::Server)
+            .map(|download| download.sha1.to_string()),
+        Some("6accb687a7dd093e047933cc7a5e4f6d0ab114d5".to_owned())
+    );
+    assert_eq!(
+        version.download_for(Side::Server),
+        version.server_download()
+    );
+}
+
+#[test]
+fn logging_types() {
+    use mc_launchermeta::version::logging::LoggingType;
+
+    let version = common::sample_version();
+    let logging = version.logging.unwrap();
+    assert_eq!(logging.client.kind, LoggingType::Log4j2Xml);
+    assert_eq!(
+        serde_json::from_str::<LoggingType>("\"logback-xml\"").unwrap(),
+        LoggingType::Other("logback-xml".to_owned())
+    );
+}
+
+#[test]
+fn summary() {
+    let version = common::sample_version();
+    let summary = version.summary().to_string();
+    assert!(summary.starts_with("1.20.4 (release)\n"));
+    assert!(summary.contains(&version.main_class));
+    assert!(summary.contains("java-runtime-gamma (17)"));
+    assert!(summary.contains(&format!("libraries:  {}", version.libraries.len())));
+    assert_eq!(summary.lines().count(), 5);
+
+    let legacy = common::legacy_version().summary().to_string();
+    assert!(legacy.contains("jre-legacy (8, assumed)"));
+}
+
+#[test]
+fn byte_order_mark() {
+    let expected = common::sample_version();
+    let mut bytes = b"\xEF\xBB\xBF".to_vec();
+    bytes.extend_from_slice(common::SAMPLE_VERSION.as_bytes());
+    bytes.extend_from_slice(b"\r\n\r\n");
+
+    assert_eq!(Version::from_slice(&bytes).unwrap(), expected);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        Version::from_reader(std::io::Cursor::new(&bytes)).unwrap(),
+        expected
+    );
+    assert_eq!(
+        Version::from_str_json(std::str::from_utf8(&bytes).unwrap()).unwrap(),
+        expected
+    );
+    // strict parsing is still available through serde_json
+    assert!(serde_json::from_slice::<Version>(&bytes).is_err());
+
+    // readers shorter than a byte order mark
+    #[cfg(feature = "std")]
+    {
+        assert!(Version::from_reader(&b"{"[..]).is_err());
+        assert!(Version::from_reader(&b"\xEF\xBB"[..]).is_err());
+    }
+}
+
+#[test]
+fn server_logging() {
+    use mc_launchermeta::version::logging::Logging;
+
+    let client = common::sample_version().logging.unwrap();
+    assert_eq!(client.for_side(Side::Client), Some(&client.client));
+    assert_eq!(client.for_side(Side::Server), None);
+
+    let mut json = serde_json::to_value(&client).unwrap();
+    let mut server = json["client"].clone();
+    server["file"]["id"] = "server-1.12.xml".into();
+    json["server"] = server;
+    let logging: Logging = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(logging.client, client.client);
+    let server = logging.for_side(Side::Server).unwrap();
+    assert_eq!(server.file.id, "server-1.12.xml");
+    assert_eq!(serde_json::to_value(&logging).unwrap(), json);
+}
+
+#[test]
+fn launcher_version_gate() {
+    let version = common::sample_version();
+    assert_eq!(version.minimum_launcher_version, 21);
+    assert!(version.requires_launcher_at_least(21));
+    assert!(!version.requires_launcher_at_least(22));
+    assert!(version.is_supported());
+    assert!(version.is_supported_by(SUPPORTED_LAUNCHER_VERSION));
+    assert!(!version.is_supported_by(18));
+
+    assert!(common::legacy_version().is_supported_by(18));
+}
+
+#[test]
+fn vanilla_main_class() {
+    let mut version = common::sample_version();
+    assert_eq!(version.main_class(), "net.minecraft.client.main.Main");
+    assert!(version.is_vanilla_main_class());
+    assert!(common::legacy_version().is_vanilla_main_class());
+
+    version.main_class = "net.fabricmc.loader.impl.launch.knot.KnotClient".to_owned();
+    assert_eq!(
+        version.main_class(),
+        "net.fabricmc.loader.impl.launch.knot.KnotClient"
+    );
+    assert!(!version.is_vanilla_main_class());
+    version.main_class = "net.minecraft.client.main.Main2".to_owned();
+    assert!(!version.is_vanilla_main_class());
+}
+
+#[test]
+fn annotated_version() {
+    let mut json: serde_json::Value = serde_json::from_str(common::SAMPLE_VERSION).unwrap();
+    json["_comment_"] = serde_json::json!(["Please don't edit this file", "Generated by a tool"]);
+    json["_minecraftVersion"] = "1.20.4".into();
+    json["clientVersion"] = "1.20.4".into();
+    let version: Version = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(version.client_version_hint(), Some("1.20.4"));
+    assert_eq!(common::sample_version().client_version_hint(), None);
+
+    // comments are dropped, the hint is kept
+    let written = serde_json::to_value(&version).unwrap();
+    assert!(written.get("_comment_").is_none());
+    assert_eq!(written["clientVersion"], "1.20.4");
+    let mut without_hint = version.clone();
+    without_hint.client_version = None;
+    assert_eq!(without_hint, common::sample_version());
+
+    let text = json.to_string();
+    let borrowed: VersionRef = serde_json::from_str(&text).unwrap();
+    // skipping the comments doesn't stop the rest from borrowing
+    assert!(matches!(borrowed.id, Cow::Borrowed(_)));
+    assert!(matches!(borrowed.libraries[0].name, Cow::Borrowed(_)));
+    assert_eq!(borrowed.into_owned(), version);
+
+    // other unknown keys are still rejected
+    json["launcherHint"] = "prism".into();
+    let err = serde_json::from_value::<Version>(json.clone()).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `launcherHint`"),
+        "{}",
+        err
+    );
+    assert!(serde_json::from_str::<VersionRef>(&json.to_string()).is_err());
+}
+
+#[test]
+fn borrowed_optional_fields() {
+    let mut json: serde_json::Value = serde_json::from_str(common::LEGACY_VERSION).unwrap();
+    json["inheritsFrom"] = "1.8.9".into();
+    json["clientVersion"] = "1.8.9".into();
+    json["libraries"][0]["url"] = "https://libraries.example.com/".into();
+    let text = json.to_string();
+    let version: VersionRef = serde_json::from_str(&text).unwrap();
+
+    let borrowed = |s: &Option<Cow<str>>| matches!(s, Some(Cow::Borrowed(_)));
+    assert!(borrowed(&version.minecraft_arguments));
+    assert!(borrowed(&version.client_version));
+    assert!(borrowed(&version.inherits_from));
+    assert!(borrowed(&version.libraries[0].url));
+    let classifiers = version
+        .libraries
+        .iter()
+        .filter_map(|library| library.downloads.as_ref()?.classifiers.as_ref())
+        .flat_map(|classifiers| classifiers.keys())
+        .collect::<Vec<_>>();
+    assert!(!classifiers.is_empty());
+    assert!(classifiers
+        .iter()
+        .all(|classifier| matches!(classifier, Cow::Borrowed(_))));
+
+    // escaped strings can't be borrowed, but still parse
+    let escaped = text.replace(
+        "\"https://libraries.example.com/\"",
+        "\"https:\\/\\/libraries.example.com\\/\"",
+    );
+    let version: VersionRef = serde_json::from_str(&escaped).unwrap();
+    assert_eq!(
+        version.libraries[0].url,
+        Some(Cow::Owned("https://libraries.example.com/".to_owned()))
+    );
+}
+
+#[test]
+fn version_diff() {
+    let old = common::sample_version();
+    assert!(old.diff(&old).is_empty());
+
+    let mut new = old.clone();
+    let removed = new.libraries.remove(5);
+    let diff = old.diff(&new);
+    assert_eq!(diff.removed_libraries, [removed.name.as_str()]);
+    assert!(diff.added_libraries.is_empty());
+    assert!(diff.changed_libraries.is_empty());
+    assert!(diff.changed_downloads.is_empty());
+    assert_eq!(diff.java_version, None);
+
+    let reverse = new.diff(&old);
+    assert_eq!(reverse.added_libraries, [removed.name.as_str()]);
+    assert!(reverse.removed_libraries.is_empty());
+
+    new.java_version = Some(JavaVersion::java_runtime_delta());
+    new.downloads.client.sha1 = Sha1::new([0; 20]);
+    new.arguments
+        .as_mut()
+        .unwrap()
+        .game
+        .push("--fullscreen".parse().unwrap());
+    let diff = old.diff(&new);
+    let java = diff.java_version.as_ref().unwrap();
+    assert_eq!(java.old, old.java_version);
+    assert_eq!(java.new, Some(JavaVersion::java_runtime_delta()));
+    assert_eq!(diff.changed_downloads.len(), 1);
+    assert_eq!(diff.changed_downloads[0].label, "client");
+    assert_eq!(
+        diff.changed_downloads[0].old,
+        Some(old.downloads.client.sha1)
+    );
+    assert_eq!(diff.added_game_arguments.len(), 1);
+    assert_eq!(diff.added_game_arguments[0].values, ["--fullscreen"]);
+    assert!(diff.removed_game_arguments.is_empty());
+
+    let mut bumped = old.clone();
+    bumped.libraries[5].name.push_str("-bumped");
+    let diff = old.diff(&bumped);
+    assert_eq!(diff.changed_libraries.len(), 1);
+    assert_eq!(diff.changed_libraries[0].old, old.libraries[5].name);
+    assert_eq!(diff.changed_libraries[0].new, bumped.libraries[5].name);
+    assert!(diff.added_libraries.is_empty() && diff.removed_libraries.is_empty());
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(
+        json["changed_libraries"][0]["new"],
+        bumped.libraries[5].name
+    );
+}